@@ -16,30 +16,39 @@ pub fn definitions(input: &Input) -> TokenStream {
         ..
     } = input;
 
-    let mut attrs = quote! {
-        #[allow(warnings)]
-        #[derive(Debug, Clone, ::serde::Serialize, ::serde::Deserialize)]
-    };
-
-    if cfg!(feature = "specta") {
-        attrs.extend(quote! {
-            #[derive(::prisma_client_rust::specta::Type)]
-            #[specta(crate = prisma_client_rust::specta)]
-        });
+    let struct_attrs = |suffix: &str| {
+        let mut attrs = quote! {
+            #[allow(warnings)]
+            #[derive(Debug, Clone, ::serde::Serialize, ::serde::Deserialize)]
+        };
+
+        if cfg!(feature = "specta") {
+            attrs.extend(quote! {
+                #[derive(::prisma_client_rust::specta::Type)]
+                #[specta(crate = prisma_client_rust::specta)]
+            });
+
+            attrs.extend(match &macro_rules.name {
+                None => quote!(#[specta(inline)]),
+                Some(name) => {
+                    let name_pascal = format!(
+                        "{}{suffix}",
+                        name.to_string().to_case(convert_case::Case::Pascal)
+                    );
+
+                    quote! {
+                        #[specta(rename = #name_pascal)]
+                    }
+                }
+            });
+        }
 
-        attrs.extend(match &macro_rules.name {
-            None => quote!(#[specta(inline)]),
-            Some(name) => {
-                let name_pascal = name.to_string().to_case(convert_case::Case::Pascal);
+        attrs
+    };
 
-                quote! {
-                    #[specta(rename = #name_pascal)]
-                }
-            }
-        });
-    }
+    let attrs = struct_attrs("");
 
-    let (fields, field_modules): (Vec<_>, Vec<_>) = schema_struct
+    let (mut fields, mut field_modules): (Vec<_>, Vec<_>) = schema_struct
         .fields
         .iter()
         .filter_map(|field| {
@@ -96,6 +105,40 @@ pub fn definitions(input: &Input) -> TokenStream {
         })
         .unzip();
 
+    if let Some(relation_count) = &macro_rules.selection.relation_count {
+        let count_fields = relation_count.iter().map(|item| {
+            let name = &item.name;
+
+            // reuse the serde rename of the relation field
+            let attrs = schema_struct
+                .fields
+                .iter()
+                .find(|field| field.ident.as_ref() == Some(name))
+                .map(|field| field.attrs.iter().collect::<Vec<_>>())
+                .unwrap_or_default();
+
+            quote! {
+                #(#attrs)*
+                pub #name: i64
+            }
+        });
+
+        let count_attrs = struct_attrs("Count");
+
+        fields.push(quote! {
+            #[serde(rename = "_count")]
+            pub _count: _count::Data
+        });
+        field_modules.push(Some(quote! {
+            pub mod _count {
+                #count_attrs
+                pub struct Data {
+                    #(#count_fields),*
+                }
+            }
+        }));
+    }
+
     quote! {
         #attrs
         pub struct Data {
@@ -1,6 +1,7 @@
 mod arg;
 mod filters;
 mod item;
+mod relation_count;
 
 use convert_case::*;
 use prisma_client_rust_generator_shared::{select_include::Variant, Arity, RelationArity};
@@ -14,6 +15,7 @@ use syn::{
 pub use arg::*;
 pub use filters::*;
 pub use item::*;
+pub use relation_count::*;
 
 use super::Input;
 
@@ -52,6 +54,27 @@ pub fn selection_to_params(input: &Input, variant: Variant) -> Vec<TokenStream>
     let variant_param = variant.param();
     let variant_pascal = format_ident!("{}", variant.to_string().to_case(Case::Pascal));
 
+    let relation_count = macro_rules.selection.relation_count.as_ref().map(|relation_count| {
+        let params = relation_count.iter().map(|RelationCountItem { name, filters }| {
+            let is_countable = selectable_fields.iter().any(|field| {
+                &field.name == name
+                    && matches!(field.arity, Arity::Relation(_, RelationArity::Many))
+            });
+
+            if !is_countable {
+                return quote_spanned!(name.span() => compile_error!("Only list relations can be counted"));
+            }
+
+            let filters = filters.clone().unwrap_or_else(|| quote!(vec![]));
+
+            quote!(#dollar::#model_path::#name::count(#filters))
+        });
+
+        quote! {
+            #dollar::#model_path::#variant_param::RelationCount(vec![#(#params),*])
+        }
+    });
+
     macro_rules
         .selection
         .iter()
@@ -123,6 +146,7 @@ pub fn selection_to_params(input: &Input, variant: Variant) -> Vec<TokenStream>
                 }
             },
         )
+        .chain(relation_count)
         .collect()
 }
 
@@ -148,11 +172,14 @@ pub fn selection_struct(input: &Input, variant: Variant) -> TokenStream {
 }
 
 #[derive(Debug)]
-pub struct Selection(Vec<SelectionItem>);
+pub struct Selection {
+    items: Vec<SelectionItem>,
+    pub relation_count: Option<RelationCountSelection>,
+}
 
 impl Selection {
     pub fn iter(&self) -> impl Iterator<Item = &SelectionItem> {
-        self.0.iter()
+        self.items.iter()
     }
 }
 
@@ -161,7 +188,7 @@ impl IntoIterator for Selection {
     type IntoIter = std::vec::IntoIter<Self::Item>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.into_iter()
+        self.items.into_iter()
     }
 }
 
@@ -170,12 +197,27 @@ impl Parse for Selection {
         let contents;
         braced!(contents in input);
 
-        let mut ret = vec![];
+        let mut items = vec![];
+        let mut relation_count = None;
 
         while !contents.is_empty() {
-            ret.push(contents.parse()?);
+            if RelationCountSelection::peek(&contents) {
+                let span = contents.span();
+
+                if relation_count.replace(contents.parse()?).is_some() {
+                    return Err(syn::Error::new(
+                        span,
+                        "_count can only be selected once, list all relations to count inside it",
+                    ));
+                }
+            } else {
+                items.push(contents.parse()?);
+            }
         }
 
-        Ok(Self(ret))
+        Ok(Self {
+            items,
+            relation_count,
+        })
     }
 }
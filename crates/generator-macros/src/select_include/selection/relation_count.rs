@@ -0,0 +1,79 @@
+use proc_macro2::{TokenStream, TokenTree};
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    *,
+};
+
+#[derive(Debug)]
+pub struct RelationCountItem {
+    pub name: Ident,
+    pub filters: Option<TokenStream>,
+}
+
+impl Parse for RelationCountItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            name: input.parse()?,
+            filters: {
+                if input.peek(Token![:]) {
+                    input.parse::<Token![:]>()?;
+
+                    // Filters are consumed up to the next top-level comma,
+                    // since commas inside vec![] etc. are contained in groups.
+                    let mut filters = TokenStream::new();
+                    while !input.is_empty() && !input.peek(Token![,]) {
+                        filters.extend([input.parse::<TokenTree>()?]);
+                    }
+
+                    Some(filters)
+                } else {
+                    None
+                }
+            },
+        })
+    }
+}
+
+/// `_count(posts, comments: vec![...])`
+#[derive(Debug)]
+pub struct RelationCountSelection(Vec<RelationCountItem>);
+
+impl RelationCountSelection {
+    pub fn peek(input: ParseStream) -> bool {
+        input
+            .fork()
+            .parse::<Ident>()
+            .map(|ident| ident == "_count")
+            .unwrap_or(false)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &RelationCountItem> {
+        self.0.iter()
+    }
+}
+
+impl Parse for RelationCountSelection {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Ident>()?;
+
+        let content;
+        parenthesized!(content in input);
+
+        let items = Punctuated::<RelationCountItem, Token![,]>::parse_terminated(&content)?
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        for (i, item) in items.iter().enumerate() {
+            if items[..i].iter().any(|prev| prev.name == item.name) {
+                return Err(syn::Error::new(
+                    item.name.span(),
+                    format!("relation '{}' is already counted", item.name),
+                ));
+            }
+        }
+
+        Ok(Self(items))
+    }
+}
@@ -46,8 +46,23 @@ pub fn generate(args: &GenerateArgs) -> TokenStream {
 
     let callback_fn = cfg!(feature = "mutation-callbacks").then(|| {
         quote! {
+            /// Adds a callback that runs after every successful write.
+            /// Writes made in a transaction are only notified once it commits.
             pub fn with_model_mutation_callback(mut self, callback: impl Fn(#pcr::ModelMutationCallbackData) + 'static + Send + Sync) -> Self {
-                self.action_notifier.model_mutation_callbacks.push(Box::new(callback));
+                self.action_notifier.add_callback(callback);
+                self
+            }
+
+            /// Adds an async callback that runs after every successful write.
+            /// The write's query doesn't finish until the callback has finished.
+            pub fn with_async_model_mutation_callback<Fut>(
+                mut self,
+                callback: impl Fn(#pcr::ModelMutationCallbackData) -> Fut + 'static + Send + Sync
+            ) -> Self
+            where
+                Fut: ::std::future::Future<Output = ()> + Send + 'static
+            {
+                self.action_notifier.add_async_callback(callback);
                 self
             }
         }
@@ -56,7 +71,7 @@ pub fn generate(args: &GenerateArgs) -> TokenStream {
     let mock_ctor = cfg!(feature = "mocking").then(|| {
         quote! {
             pub fn _mock() -> (Self, #pcr::MockStore) {
-                let (internals, store) = #pcr::PrismaClientInternals::new_mock(#pcr::ActionNotifier::new());
+                let (internals, store) = #pcr::PrismaClientInternals::new_mock(#pcr::ActionNotifier::new(), vec![], None);
 
                 (Self(internals), store)
             }
@@ -96,14 +111,26 @@ pub fn generate(args: &GenerateArgs) -> TokenStream {
     quote! {
         pub struct PrismaClientBuilder {
             url: Option<String>,
+            replica_urls: Vec<String>,
             action_notifier: #pcr::ActionNotifier,
+            middleware: Vec<Box<dyn #pcr::Middleware>>,
+            slow_query_threshold: Option<::std::time::Duration>,
+            query_timeout: Option<::std::time::Duration>,
+            connection_options: #pcr::ConnectionOptions,
+            lazy_connect: bool,
         }
 
         impl PrismaClientBuilder {
             fn new() -> Self {
                 Self {
                     url: None,
-                    action_notifier: #pcr::ActionNotifier::new()
+                    replica_urls: vec![],
+                    action_notifier: #pcr::ActionNotifier::new(),
+                    middleware: vec![],
+                    slow_query_threshold: None,
+                    query_timeout: None,
+                    connection_options: Default::default(),
+                    lazy_connect: false,
                 }
             }
 
@@ -112,13 +139,95 @@ pub fn generate(args: &GenerateArgs) -> TokenStream {
                 self
             }
 
+            /// Adds a read replica of the database.
+            /// Reads outside of transactions are distributed across the replicas using round-robin,
+            /// while writes, raw queries, batches and transactions use the primary database.
+            pub fn with_replica_url(mut self, url: String) -> Self {
+                self.replica_urls.push(url);
+                self
+            }
+
             #callback_fn
 
+            /// Adds a middleware that runs around every query the client executes.
+            /// Middleware runs in the order it is added.
+            pub fn with_middleware(mut self, middleware: impl #pcr::Middleware) -> Self {
+                self.middleware.push(Box::new(middleware));
+                self
+            }
+
+            /// Logs a warning containing a query's arguments when it takes longer than `threshold`.
+            pub fn slow_query_threshold(mut self, threshold: ::std::time::Duration) -> Self {
+                self.slow_query_threshold = Some(threshold);
+                self
+            }
+
+            /// Cancels queries and batches that take longer than `timeout`,
+            /// unless they set their own timeout.
+            pub fn query_timeout(mut self, timeout: ::std::time::Duration) -> Self {
+                self.query_timeout = Some(timeout);
+                self
+            }
+
+            /// The maximum number of connections in the pool.
+            pub fn connection_limit(mut self, limit: u32) -> Self {
+                self.connection_options.connection_limit = Some(limit);
+                self
+            }
+
+            /// How long to wait for a connection from the pool before failing.
+            pub fn pool_timeout(mut self, timeout: ::std::time::Duration) -> Self {
+                self.connection_options.pool_timeout = Some(timeout);
+                self
+            }
+
+            /// How long to wait for a new connection to be established.
+            pub fn connect_timeout(mut self, timeout: ::std::time::Duration) -> Self {
+                self.connection_options.connect_timeout = Some(timeout);
+                self
+            }
+
+            /// How long to wait for a query's response.
+            /// For SQLite this sets the `busy_timeout` pragma.
+            pub fn socket_timeout(mut self, timeout: ::std::time::Duration) -> Self {
+                self.connection_options.socket_timeout = Some(timeout);
+                self
+            }
+
+            /// How many prepared statements are cached per connection.
+            pub fn statement_cache_size(mut self, size: u32) -> Self {
+                self.connection_options.statement_cache_size = Some(size);
+                self
+            }
+
+            /// Sets SQLite's `journal_mode` pragma when the client connects.
+            pub fn sqlite_journal_mode(mut self, journal_mode: #pcr::SqliteJournalMode) -> Self {
+                self.connection_options.sqlite_journal_mode = Some(journal_mode);
+                self
+            }
+
+            /// Don't connect to the database in `build`,
+            /// instead connecting when the first query is executed or `_connect` is called.
+            /// Useful when the database may not be available yet when the client is created.
+            pub fn lazy_connect(mut self) -> Self {
+                self.lazy_connect = true;
+                self
+            }
+
             pub async fn build(self) -> Result<PrismaClient, #pcr::NewClientError> {
                 let internals = #pcr::PrismaClientInternals::new(
                     self.url,
+                    self.replica_urls,
                     self.action_notifier,
-                    super::DATAMODEL_STR
+                    self.middleware,
+                    self.slow_query_threshold,
+                    self.query_timeout,
+                    self.connection_options,
+                    self.lazy_connect,
+                    #pcr::EmbeddedSchema {
+                        datamodel: super::DATAMODEL_STR,
+                        path: super::SCHEMA_PATH,
+                    },
                 ).await?;
 
                 Ok(PrismaClient(internals))
@@ -147,10 +256,58 @@ pub fn generate(args: &GenerateArgs) -> TokenStream {
                 #pcr::batch(queries, &self.0).await
             }
 
+            pub async fn _batch_with_options<'batch, T: #pcr::BatchContainer<'batch, Marker>, Marker>(&self, queries: T, options: #pcr::BatchOptions) -> #pcr::Result<<T as #pcr::BatchContainer<'batch, Marker>>::ReturnType> {
+                #pcr::batch_with_options(queries, &self.0, options).await
+            }
+
+            /// A client that executes every query on the primary database,
+            /// for reading data that was just written without waiting for it to reach the replicas.
+            pub fn _primary(&self) -> Self {
+                Self(self.0.primary())
+            }
+
             pub fn _transaction(&self) -> #pcr::TransactionBuilder<Self> {
                 #pcr::TransactionBuilder::_new(self, &self.0)
             }
 
+            /// Runs `hook` once this client's transaction commits, or immediately if the client isn't in a transaction.
+            pub fn _on_commit(&self, hook: impl FnOnce() + Send + 'static) {
+                self.0.on_commit(hook)
+            }
+
+            /// Runs `hook` if this client's transaction rolls back.
+            pub fn _on_rollback(&self, hook: impl FnOnce() + Send + 'static) {
+                self.0.on_rollback(hook)
+            }
+
+            /// Connects to the database and its replicas if a connection hasn't been established yet,
+            /// returning how long it took.
+            pub async fn _connect(&self) -> #pcr::Result<::std::time::Duration> {
+                self.0.connect().await
+            }
+
+            /// Runs a trivial query to check that the database and its replicas are reachable,
+            /// returning the round trip latency.
+            pub async fn _health_check(&self) -> #pcr::Result<::std::time::Duration> {
+                self.0.health_check().await
+            }
+
+            /// Stops the client from starting new queries or transactions,
+            /// waits for in-flight queries to finish, then closes the database connections.
+            pub async fn _disconnect(&self) {
+                self.0.disconnect().await
+            }
+
+            /// The provider, redacted URL and pool options the client connected with, for logging.
+            pub fn _connection_config(&self) -> #pcr::ConnectionConfig {
+                self.0.connection_config()
+            }
+
+            /// A snapshot of the metrics collected by this client and its transactions.
+            pub fn _metrics(&self) -> #pcr::Metrics {
+                self.0.metrics()
+            }
+
             #migrate_fns
 
             #(#model_actions)*
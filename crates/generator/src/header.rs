@@ -38,6 +38,7 @@ pub fn generate(args: &GenerateArgs) -> TokenStream {
     quote! {
         pub static DATAMODEL_STR: &'static str = include_str!(#schema_path_str);
         static DATABASE_STR: &'static str = #database_string;
+        static SCHEMA_PATH: &'static str = #schema_path_str;
 
         #migrations_include
     }
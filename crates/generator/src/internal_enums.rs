@@ -16,13 +16,68 @@ fn sort_order_impl() -> TokenStream {
         }
     }
 }
+
+fn sort_order_input_impl() -> TokenStream {
+    let pcr = quote!(::prisma_client_rust);
+
+    quote! {
+        /// A sort order for a nullable field, optionally specifying where nulls are placed.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct SortOrderInput {
+            pub sort: SortOrder,
+            pub nulls: Option<NullsOrder>,
+        }
+
+        impl SortOrderInput {
+            pub fn nulls_first(self) -> Self {
+                Self {
+                    nulls: Some(NullsOrder::First),
+                    ..self
+                }
+            }
+
+            pub fn nulls_last(self) -> Self {
+                Self {
+                    nulls: Some(NullsOrder::Last),
+                    ..self
+                }
+            }
+        }
+
+        impl SortOrder {
+            pub fn nulls_first(self) -> SortOrderInput {
+                SortOrderInput::from(self).nulls_first()
+            }
+
+            pub fn nulls_last(self) -> SortOrderInput {
+                SortOrderInput::from(self).nulls_last()
+            }
+        }
+
+        impl From<SortOrder> for SortOrderInput {
+            fn from(sort: SortOrder) -> Self {
+                Self { sort, nulls: None }
+            }
+        }
+
+        impl Into<#pcr::PrismaValue> for SortOrderInput {
+            fn into(self) -> #pcr::PrismaValue {
+                match self.nulls {
+                    Some(nulls) => #pcr::PrismaValue::Object(vec![
+                        ("sort".to_string(), self.sort.into()),
+                        ("nulls".to_string(), #pcr::PrismaValue::String(nulls.to_string())),
+                    ]),
+                    None => self.sort.into(),
+                }
+            }
+        }
+    }
+}
+
 pub fn generate(args: &GenerateArgs) -> TokenStream {
-    let internal_enums = args
-        .dmmf
-        .schema
-        .enum_types
-        .get("prisma")
-        .unwrap()
+    let prisma_enums = args.dmmf.schema.enum_types.get("prisma").unwrap();
+
+    let internal_enums = prisma_enums
         .iter()
         .map(|e| {
             let name = pascal_ident(&e.name);
@@ -74,9 +129,16 @@ pub fn generate(args: &GenerateArgs) -> TokenStream {
 
     let sort_order_impl = sort_order_impl();
 
+    // connectors that can't order nulls (eg. MongoDB) don't have a NullsOrder enum
+    let sort_order_input_impl = prisma_enums
+        .iter()
+        .any(|e| e.name == "NullsOrder")
+        .then(sort_order_input_impl);
+
     quote! {
         #(#internal_enums)*
 
         #sort_order_impl
+        #sort_order_input_impl
     }
 }
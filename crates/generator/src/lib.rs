@@ -52,6 +52,7 @@ impl PrismaGenerator for Generator {
                 #header
 
                 pub use _prisma::*;
+                pub use prisma_client_rust::TimeoutQuery;
                 use prisma_client_rust::scalar_types::*;
 
                 #enums
@@ -7,7 +7,7 @@ use prisma_client_rust_sdk::{
 use super::required_fields;
 
 pub fn create_fn(model: ModelWalker) -> Option<TokenStream> {
-    let (names, (types, push_wrapper)): (Vec<_>, (Vec<_>, Vec<_>)) = required_fields(model)?
+    let (names, (types, push_wrapper)): (Vec<_>, (Vec<_>, Vec<_>)) = required_fields(model, &quote!(super::))?
         .into_iter()
         .map(|field| {
             (
@@ -42,7 +42,7 @@ pub fn create_fn(model: ModelWalker) -> Option<TokenStream> {
 }
 
 pub fn create_unchecked_fn(model: ModelWalker) -> Option<TokenStream> {
-    required_fields(model)?;
+    required_fields(model, &quote!(super::))?;
 
     let (names, types): (Vec<_>, Vec<_>) = model
         .scalar_fields()
@@ -105,7 +105,7 @@ pub fn create_many_fn(model: ModelWalker) -> Option<TokenStream> {
 
 pub fn upsert_fn(model: ModelWalker) -> Option<TokenStream> {
     // necessary to check whether CreateData is even available
-    let _ = required_fields(model)?;
+    let _ = required_fields(model, &quote!(super::))?;
 
     Some(quote! {
         pub fn upsert(
@@ -166,6 +166,13 @@ pub fn struct_definition(model: ModelWalker, args: &GenerateArgs) -> TokenStream
                 )
             }
 
+            pub fn find_unique_or_throw(self, _where: UniqueWhereParam) -> FindUniqueOrThrowQuery<'a> {
+                FindUniqueOrThrowQuery::new(
+                    self.client,
+                    _where
+                )
+            }
+
             pub fn find_first(self, _where: Vec<WhereParam>) -> FindFirstQuery<'a> {
                 FindFirstQuery::new(
                     self.client,
@@ -173,6 +180,13 @@ pub fn struct_definition(model: ModelWalker, args: &GenerateArgs) -> TokenStream
                 )
             }
 
+            pub fn find_first_or_throw(self, _where: Vec<WhereParam>) -> FindFirstOrThrowQuery<'a> {
+                FindFirstOrThrowQuery::new(
+                    self.client,
+                    _where
+                )
+            }
+
             pub fn find_many(self, _where: Vec<WhereParam>) -> FindManyQuery<'a> {
                 FindManyQuery::new(
                     self.client,
@@ -228,6 +242,13 @@ pub fn struct_definition(model: ModelWalker, args: &GenerateArgs) -> TokenStream
                 )
             }
 
+            pub fn exists(self, _where: Vec<WhereParam>) -> ExistsQuery<'a> {
+                ExistsQuery::new(
+                    self.client,
+                    _where
+                )
+            }
+
             pub fn count(self, _where: Vec<WhereParam>) -> CountQuery<'a> {
                 CountQuery::new(
                     self.client,
@@ -235,6 +256,21 @@ pub fn struct_definition(model: ModelWalker, args: &GenerateArgs) -> TokenStream
                 )
             }
 
+            pub fn aggregate(self, _where: Vec<WhereParam>) -> AggregateQuery<'a> {
+                AggregateQuery::new(
+                    self.client,
+                    _where
+                )
+            }
+
+            pub fn group_by(self, by: Vec<ScalarField>, _where: Vec<WhereParam>) -> GroupByQuery<'a> {
+                GroupByQuery::new(
+                    self.client,
+                    by,
+                    _where
+                )
+            }
+
             #monogo_raw_fns
         }
     }
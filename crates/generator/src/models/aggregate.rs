@@ -0,0 +1,234 @@
+use std::collections::BTreeMap;
+
+use prisma_client_rust_sdk::prisma::{
+    prisma_models::{
+        walkers::{ModelWalker, ScalarFieldWalker},
+        FieldArity,
+    },
+    psl::parser_database::{ParserDatabase, ScalarFieldType, ScalarType},
+};
+
+use crate::prelude::*;
+
+use super::ModelModulePart;
+
+#[derive(Clone, Copy)]
+pub enum Function {
+    Count,
+    Avg,
+    Sum,
+    Min,
+    Max,
+}
+
+impl Function {
+    pub const ALL: [Self; 5] = [Self::Count, Self::Avg, Self::Sum, Self::Min, Self::Max];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Count => "count",
+            Self::Avg => "avg",
+            Self::Sum => "sum",
+            Self::Min => "min",
+            Self::Max => "max",
+        }
+    }
+
+    /// The type a field resolves to when aggregated with this function,
+    /// or `None` if the function can't be applied to the field.
+    pub fn result_type(&self, field: ScalarFieldWalker) -> Option<ResultType> {
+        if field.ast_field().arity.is_list() && !matches!(self, Self::Count) {
+            return None;
+        }
+
+        Some(match (self, field.scalar_field_type()) {
+            (_, ScalarFieldType::CompositeType(_) | ScalarFieldType::Unsupported(_)) => {
+                return None
+            }
+            (Self::Count, _) => ResultType::Count,
+            (Self::Avg, typ @ ScalarFieldType::BuiltInScalar(ScalarType::Decimal)) => {
+                ResultType::Scalar(typ)
+            }
+            (
+                Self::Avg,
+                ScalarFieldType::BuiltInScalar(
+                    ScalarType::Int | ScalarType::BigInt | ScalarType::Float,
+                ),
+            ) => ResultType::Scalar(ScalarFieldType::BuiltInScalar(ScalarType::Float)),
+            (
+                Self::Sum,
+                typ @ ScalarFieldType::BuiltInScalar(
+                    ScalarType::Int | ScalarType::BigInt | ScalarType::Float | ScalarType::Decimal,
+                ),
+            ) => ResultType::Scalar(typ),
+            (
+                Self::Min | Self::Max,
+                ScalarFieldType::BuiltInScalar(ScalarType::Json | ScalarType::Bytes),
+            ) => return None,
+            (Self::Min | Self::Max, typ) => ResultType::Scalar(typ),
+            _ => return None,
+        })
+    }
+}
+
+pub enum ResultType {
+    Count,
+    Scalar(ScalarFieldType),
+}
+
+impl ResultType {
+    pub fn to_tokens(&self, prefix: &TokenStream, db: &ParserDatabase) -> Option<TokenStream> {
+        match self {
+            Self::Count => Some(quote!(i64)),
+            Self::Scalar(typ) => typ.to_tokens(prefix, &FieldArity::Required, db),
+        }
+    }
+
+    pub fn to_prisma_value(&self, var: &Ident) -> Option<TokenStream> {
+        match self {
+            Self::Count => Some(quote!(::prisma_client_rust::PrismaValue::Int(#var as i32))),
+            Self::Scalar(typ) => typ.to_prisma_value(var, &FieldArity::Required),
+        }
+    }
+}
+
+pub fn model_data(model: ModelWalker) -> ModelModulePart {
+    let pcr = quote!(::prisma_client_rust);
+
+    let mut variants = vec![quote!(Count)];
+    let mut into_arms = vec![quote! {
+        Self::Count => (#pcr::AggregateFunction::Count, "_all".to_string())
+    }];
+    let mut result_fields = Function::ALL
+        .iter()
+        .map(|function| (function.name(), vec![]))
+        .collect::<BTreeMap<_, _>>();
+    let mut field_fns = BTreeMap::new();
+
+    result_fields.get_mut("count").unwrap().push(quote! {
+        #[serde(rename = "_all", default)]
+        pub _all: Option<i64>
+    });
+
+    for field in model.scalar_fields() {
+        let field_name_str = field.name();
+        let field_name_snake = snake_ident(field_name_str);
+        let field_name_pascal = pascal_ident(field_name_str);
+
+        for function in Function::ALL {
+            let function_name_snake = snake_ident(function.name());
+            let function_name_pascal = pascal_ident(function.name());
+
+            let Some(result_type) = function
+                .result_type(field)
+                .and_then(|typ| typ.to_tokens(&quote!(super::), field.db))
+            else {
+                continue;
+            };
+
+            let variant_name = format_ident!("{function_name_pascal}{field_name_pascal}");
+
+            variants.push(quote!(#variant_name));
+            into_arms.push(quote! {
+                Self::#variant_name => (
+                    #pcr::AggregateFunction::#function_name_pascal,
+                    #field_name_snake::NAME.to_string()
+                )
+            });
+
+            result_fields
+                .get_mut(function.name())
+                .unwrap()
+                .push(quote! {
+                    #[serde(rename = #field_name_str, default)]
+                    pub #field_name_snake: Option<#result_type>
+                });
+
+            field_fns
+                .entry(field_name_str.to_string())
+                .or_insert_with(TokenStream::new)
+                .extend(quote! {
+                    pub fn #function_name_snake() -> AggregateParam {
+                        AggregateParam::#variant_name
+                    }
+                });
+        }
+    }
+
+    let result_structs = Function::ALL.iter().map(|function| {
+        let struct_name = format_ident!("{}AggregateResult", pascal_ident(function.name()));
+        let fields = &result_fields[function.name()];
+
+        let specta_derive = cfg!(feature = "specta").then(|| {
+            let struct_name_str = format!("{}{struct_name}", pascal_ident(model.name()));
+
+            quote! {
+                #[derive(::prisma_client_rust::specta::Type)]
+                #[specta(rename = #struct_name_str, crate = prisma_client_rust::specta)]
+            }
+        });
+
+        quote! {
+            #[derive(Debug, Clone, Default, ::serde::Serialize, ::serde::Deserialize)]
+            #specta_derive
+            pub struct #struct_name {
+                #(#fields),*
+            }
+        }
+    });
+
+    let (result_field_names, result_field_types): (Vec<_>, Vec<_>) = Function::ALL
+        .iter()
+        .map(|function| {
+            (
+                snake_ident(function.name()),
+                format_ident!("{}AggregateResult", pascal_ident(function.name())),
+            )
+        })
+        .unzip();
+    let result_field_names_str = Function::ALL
+        .iter()
+        .map(|function| format!("_{}", function.name()));
+
+    let specta_derive = cfg!(feature = "specta").then(|| {
+        let struct_name_str = format!("{}AggregateResult", pascal_ident(model.name()));
+
+        quote! {
+            #[derive(::prisma_client_rust::specta::Type)]
+            #[specta(rename = #struct_name_str, crate = prisma_client_rust::specta)]
+        }
+    });
+
+    ModelModulePart {
+        data: quote! {
+            #[derive(Debug, Clone, Copy)]
+            pub enum AggregateParam {
+                #(#variants),*
+            }
+
+            impl Into<(#pcr::AggregateFunction, String)> for AggregateParam {
+                fn into(self) -> (#pcr::AggregateFunction, String) {
+                    match self {
+                        #(#into_arms),*
+                    }
+                }
+            }
+
+            pub fn count_all() -> AggregateParam {
+                AggregateParam::Count
+            }
+
+            #(#result_structs)*
+
+            #[derive(Debug, Clone, Default, ::serde::Serialize, ::serde::Deserialize)]
+            #specta_derive
+            pub struct AggregateResult {
+                #(
+                    #[serde(rename = #result_field_names_str, default)]
+                    pub #result_field_names: #result_field_types
+                ),*
+            }
+        },
+        fields: field_fns,
+    }
+}
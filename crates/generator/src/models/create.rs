@@ -7,7 +7,7 @@ use crate::prelude::*;
 use super::required_fields;
 
 fn create_unchecked(model: ModelWalker) -> Option<TokenStream> {
-    required_fields(model)?;
+    required_fields(model, &quote!(super::))?;
 
     let model_name_snake = snake_ident(model.name());
 
@@ -74,7 +74,7 @@ fn create_unchecked(model: ModelWalker) -> Option<TokenStream> {
 fn create(model: ModelWalker) -> Option<TokenStream> {
     let model_name_snake = snake_ident(model.name());
 
-    let (names, (types, push_wrappers)): (Vec<_>, (Vec<_>, Vec<_>)) = required_fields(model)?
+    let (names, (types, push_wrappers)): (Vec<_>, (Vec<_>, Vec<_>)) = required_fields(model, &quote!(super::))?
         .into_iter()
         .map(|field| {
             (
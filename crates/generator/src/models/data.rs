@@ -8,7 +8,7 @@ use prisma_client_rust_sdk::prisma::{
 
 use crate::prelude::*;
 
-use super::ModelModulePart;
+use super::{relation_count, ModelModulePart};
 
 pub fn model_data(model: ModelWalker) -> ModelModulePart {
     let fields = model
@@ -126,6 +126,16 @@ pub fn r#struct(model: ModelWalker) -> TokenStream {
             }
         });
 
+    let relation_count_field = relation_count::countable_relations(model).next().map(|_| {
+        let specta_attrs = cfg!(feature = "specta").then(|| quote!(#[specta(skip)]));
+
+        quote! {
+            #[serde(rename = "_count", default, skip_serializing_if = "Option::is_none")]
+            #specta_attrs
+            pub _count: Option<RelationCount>
+        }
+    });
+
     let relation_accessors = model.fields().filter_map(|field| match field.refine() {
         RefinedFieldWalker::Relation(relation_field) => {
             let field_name_snake = snake_ident(field.name());
@@ -168,7 +178,8 @@ pub fn r#struct(model: ModelWalker) -> TokenStream {
         #[derive(Debug, Clone, ::serde::Serialize, ::serde::Deserialize)]
         #specta_derive
         pub struct Data {
-            #(#struct_fields),*
+            #(#struct_fields,)*
+            #relation_count_field
         }
 
         impl Data {
@@ -0,0 +1,311 @@
+use std::collections::BTreeMap;
+
+use prisma_client_rust_sdk::prisma::{
+    dmmf::TypeLocation, prisma_models::walkers::ModelWalker, psl::parser_database::ScalarFieldType,
+};
+
+use crate::prelude::*;
+
+use super::{aggregate::Function, ModelModulePart};
+
+static HAVING_FILTERS: &[(&str, &str, bool)] = &[
+    ("equals", "Equals", false),
+    ("not", "Not", false),
+    ("in_vec", "InVec", true),
+    ("not_in_vec", "NotInVec", true),
+    ("lt", "Lt", false),
+    ("lte", "Lte", false),
+    ("gt", "Gt", false),
+    ("gte", "Gte", false),
+];
+
+pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
+    let pcr = quote!(::prisma_client_rust);
+
+    let mut having_variants = vec![];
+    let mut having_arms = vec![];
+    let mut order_by_variants = vec![];
+    let mut order_by_arms = vec![];
+    let mut result_fields = vec![];
+    let mut field_fns = BTreeMap::<String, TokenStream>::new();
+    let mut function_fns = BTreeMap::<(String, &'static str), TokenStream>::new();
+
+    let order_by_input = args
+        .dmmf
+        .schema
+        .find_input_type(&format!("{}OrderByWithAggregationInput", model.name()));
+
+    for field in model.scalar_fields() {
+        if matches!(
+            field.scalar_field_type(),
+            ScalarFieldType::CompositeType(_) | ScalarFieldType::Unsupported(_)
+        ) {
+            continue;
+        }
+
+        let field_name_str = field.name();
+        let field_name_snake = snake_ident(field_name_str);
+        let field_name_pascal = pascal_ident(field_name_str);
+
+        let result_field_attrs = match field.ast_field().arity.is_optional() {
+            true => quote! {
+                #[serde(
+                    rename = #field_name_str,
+                    default,
+                    with = "::prisma_client_rust::serde::double_option"
+                )]
+            },
+            false => quote!(#[serde(rename = #field_name_str, default)]),
+        };
+
+        result_fields.push(quote! {
+            #result_field_attrs
+            pub #field_name_snake: Option<#field_name_snake::Type>
+        });
+
+        let order_by_field =
+            order_by_input.and_then(|input| input.fields.iter().find(|f| f.name == field_name_str));
+
+        let is_sortable = order_by_field
+            .map(|f| matches!(f.input_types[0].location, TypeLocation::EnumTypes))
+            .unwrap_or(false);
+
+        if is_sortable {
+            // nullable fields accept { sort, nulls }, the same as their Order type
+            let sort_type = match order_by_field
+                .map(|f| f.input_types.iter().any(|t| t.typ == "SortOrderInput"))
+                .unwrap_or(false)
+            {
+                true => quote!(SortOrderInput),
+                false => quote!(SortOrder),
+            };
+
+            order_by_variants.push(quote!(#field_name_pascal(#sort_type)));
+            order_by_arms.push(quote! {
+                Self::#field_name_pascal(direction) => (
+                    #field_name_snake::NAME.to_string(),
+                    direction.into()
+                )
+            });
+
+            field_fns
+                .entry(field_name_str.to_string())
+                .or_default()
+                .extend(quote! {
+                    impl From<Order> for super::OrderByWithAggregationParam {
+                        fn from(Order(v): Order) -> Self {
+                            Self::#field_name_pascal(v)
+                        }
+                    }
+                });
+        }
+
+        for function in Function::ALL {
+            let function_name_str = format!("_{}", function.name());
+            let function_name_pascal = pascal_ident(function.name());
+            let variant_name = format_ident!("{function_name_pascal}{field_name_pascal}");
+
+            let result_type = function
+                .result_type(field)
+                .filter(|_| !field.ast_field().arity.is_list());
+
+            if let Some(result_type) = result_type {
+                let value = format_ident!("value");
+
+                let (Some(typ), Some(pv)) = (
+                    result_type.to_tokens(&quote!(), field.db),
+                    result_type.to_prisma_value(&value),
+                ) else {
+                    continue;
+                };
+
+                having_variants.push(quote!(#variant_name(#pcr::AggregateFilter<#typ>)));
+                having_arms.push(quote! {
+                    Self::#variant_name(filter) => (
+                        #field_name_snake::NAME,
+                        #pcr::SerializedWhereValue::Object(vec![(
+                            #function_name_str.to_string(),
+                            #pcr::PrismaValue::Object(vec![filter.serialize(|#value| #pv)])
+                        )])
+                    )
+                });
+
+                let filter_fns = HAVING_FILTERS.iter().map(|(name, variant, list)| {
+                    let name = format_ident!("{name}");
+                    let variant = format_ident!("{variant}");
+                    let typ = match list {
+                        true => quote!(Vec<#typ>),
+                        false => quote!(#typ),
+                    };
+
+                    quote! {
+                        pub fn #name(value: #typ) -> super::super::HavingParam {
+                            super::super::HavingParam::#variant_name(
+                                #pcr::AggregateFilter::#variant(value)
+                            )
+                        }
+                    }
+                });
+
+                function_fns
+                    .entry((field_name_str.to_string(), function.name()))
+                    .or_default()
+                    .extend(quote!(#(#filter_fns)*));
+            }
+
+            let is_sortable = args
+                .dmmf
+                .schema
+                .find_input_type(&format!(
+                    "{}{function_name_pascal}OrderByAggregateInput",
+                    model.name()
+                ))
+                .map(|input| input.fields.iter().any(|f| f.name == field_name_str))
+                .unwrap_or(false);
+
+            if is_sortable {
+                order_by_variants.push(quote!(#variant_name(SortOrder)));
+                order_by_arms.push(quote! {
+                    Self::#variant_name(direction) => (
+                        #function_name_str.to_string(),
+                        #pcr::PrismaValue::Object(vec![(
+                            #field_name_snake::NAME.to_string(),
+                            direction.into()
+                        )])
+                    )
+                });
+
+                function_fns
+                    .entry((field_name_str.to_string(), function.name()))
+                    .or_default()
+                    .extend(quote! {
+                        pub fn order(direction: SortOrder) -> super::super::OrderByWithAggregationParam {
+                            super::super::OrderByWithAggregationParam::#variant_name(direction)
+                        }
+                    });
+            }
+        }
+    }
+
+    // prefixed like the engine's `_avg` etc. so they don't clash with the aggregate selector fns
+    for ((field_name, function_name), fns) in function_fns {
+        let module_name = format_ident!("_{function_name}");
+
+        field_fns.entry(field_name).or_default().extend(quote! {
+            pub mod #module_name {
+                use super::*;
+
+                #fns
+            }
+        });
+    }
+
+    let specta_derive = cfg!(feature = "specta").then(|| {
+        let struct_name_str = format!("{}GroupByResult", pascal_ident(model.name()));
+
+        quote! {
+            #[derive(::prisma_client_rust::specta::Type)]
+            #[specta(rename = #struct_name_str, crate = prisma_client_rust::specta)]
+        }
+    });
+
+    ModelModulePart {
+        data: quote! {
+            #[derive(Debug, Clone)]
+            pub enum HavingParam {
+                Not(Vec<HavingParam>),
+                Or(Vec<HavingParam>),
+                And(Vec<HavingParam>),
+                Where(WhereParam),
+                #(#having_variants),*
+            }
+
+            impl #pcr::WhereInput for HavingParam {
+                fn serialize(self) -> #pcr::SerializedWhereInput {
+                    let (name, value) = match self {
+                        Self::Not(value) => (
+                            "NOT",
+                            #pcr::SerializedWhereValue::Object(#pcr::merge_fields(
+                                value
+                                    .into_iter()
+                                    .map(#pcr::WhereInput::serialize)
+                                    .map(Into::into)
+                                    .collect()
+                            ))
+                        ),
+                        Self::Or(value) => (
+                            "OR",
+                            #pcr::SerializedWhereValue::List(
+                                value
+                                    .into_iter()
+                                    .map(#pcr::WhereInput::serialize)
+                                    .map(|p| #pcr::PrismaValue::Object(vec![p.into()]))
+                                    .collect()
+                            )
+                        ),
+                        Self::And(value) => (
+                            "AND",
+                            #pcr::SerializedWhereValue::Object(#pcr::merge_fields(
+                                value
+                                    .into_iter()
+                                    .map(#pcr::WhereInput::serialize)
+                                    .map(Into::into)
+                                    .collect()
+                            ))
+                        ),
+                        Self::Where(param) => return #pcr::WhereInput::serialize(param),
+                        #(#having_arms),*
+                    };
+
+                    #pcr::SerializedWhereInput::new(name.to_string(), value)
+                }
+            }
+
+            impl From<#pcr::Operator<Self>> for HavingParam {
+                fn from(op: #pcr::Operator<Self>) -> Self {
+                    match op {
+                        #pcr::Operator::Not(value) => Self::Not(value),
+                        #pcr::Operator::And(value) => Self::And(value),
+                        #pcr::Operator::Or(value) => Self::Or(value),
+                    }
+                }
+            }
+
+            impl From<WhereParam> for HavingParam {
+                fn from(param: WhereParam) -> Self {
+                    Self::Where(param)
+                }
+            }
+
+            #[derive(Debug, Clone)]
+            pub enum OrderByWithAggregationParam {
+                #(#order_by_variants),*
+            }
+
+            impl Into<(String, #pcr::PrismaValue)> for OrderByWithAggregationParam {
+                fn into(self) -> (String, #pcr::PrismaValue) {
+                    match self {
+                        #(#order_by_arms),*
+                    }
+                }
+            }
+
+            #[derive(Debug, Clone, ::serde::Serialize, ::serde::Deserialize)]
+            #specta_derive
+            pub struct GroupByResult {
+                #(#result_fields,)*
+                #[serde(default)]
+                pub _count: CountAggregateResult,
+                #[serde(default)]
+                pub _avg: AvgAggregateResult,
+                #[serde(default)]
+                pub _sum: SumAggregateResult,
+                #[serde(default)]
+                pub _min: MinAggregateResult,
+                #[serde(default)]
+                pub _max: MaxAggregateResult,
+            }
+        },
+        fields: field_fns,
+    }
+}
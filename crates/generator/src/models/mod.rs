@@ -1,10 +1,13 @@
 mod actions;
+mod aggregate;
 mod create;
 mod data;
 mod filter;
+mod group_by;
 mod order_by;
 mod pagination;
 mod partial_unchecked;
+mod relation_count;
 mod select_include;
 mod set_params;
 mod types;
@@ -28,7 +31,10 @@ pub struct RequiredField<'a> {
     pub inner: FieldWalker<'a>,
 }
 
-pub fn required_fields<'a>(model: ModelWalker<'a>) -> Option<Vec<RequiredField<'a>>> {
+pub fn required_fields<'a>(
+    model: ModelWalker<'a>,
+    prefix: &TokenStream,
+) -> Option<Vec<RequiredField<'a>>> {
     model
         .fields()
         .filter(|field| match field.refine() {
@@ -48,16 +54,16 @@ pub fn required_fields<'a>(model: ModelWalker<'a>) -> Option<Vec<RequiredField<'
 
                                 let type_snake = snake_ident(comp_type.name());
 
-                                quote!(super::#type_snake::Create)
+                                quote!(#prefix #type_snake::Create)
                             }
-                            _ => field.type_tokens(&quote!(super::))?,
+                            _ => field.type_tokens(prefix)?,
                         }
                     }
                     RefinedFieldWalker::Relation(relation_field) => {
                         let relation_model_name_snake =
                             snake_ident(relation_field.related_model().name());
 
-                        quote!(super::#relation_model_name_snake::UniqueWhereParam)
+                        quote!(#prefix #relation_model_name_snake::UniqueWhereParam)
                     }
                 };
 
@@ -93,6 +99,9 @@ pub fn modules(args: &GenerateArgs, module_path: &TokenStream) -> Vec<Module> {
                 order_by::model_data(model, args),
                 with_params::model_data(model),
                 set_params::model_data(model, args),
+                aggregate::model_data(model),
+                group_by::model_data(model, args),
+                relation_count::model_data(model),
                 select::model_data(model, &module_path),
                 include::model_data(model, &module_path),
             ]);
@@ -126,12 +135,17 @@ pub fn modules(args: &GenerateArgs, module_path: &TokenStream) -> Vec<Module> {
                     pub type ManyArgs = #pcr::ManyArgs<Types>;
 
                     pub type CountQuery<'a> = #pcr::Count<'a, Types>;
+                    pub type AggregateQuery<'a> = #pcr::Aggregate<'a, Types>;
+                    pub type GroupByQuery<'a> = #pcr::GroupBy<'a, Types>;
                     pub type CreateQuery<'a> = #pcr::Create<'a, Types>;
                     pub type CreateUncheckedQuery<'a> = #pcr::CreateUnchecked<'a, Types>;
                     pub type CreateManyQuery<'a> = #pcr::CreateMany<'a, Types>;
                     pub type FindUniqueQuery<'a> = #pcr::FindUnique<'a, Types>;
+                    pub type FindUniqueOrThrowQuery<'a> = #pcr::FindUniqueOrThrow<'a, Types>;
                     pub type FindManyQuery<'a> = #pcr::FindMany<'a, Types>;
                     pub type FindFirstQuery<'a> = #pcr::FindFirst<'a, Types>;
+                    pub type FindFirstOrThrowQuery<'a> = #pcr::FindFirstOrThrow<'a, Types>;
+                    pub type ExistsQuery<'a> = #pcr::Exists<'a, Types>;
                     pub type UpdateQuery<'a> = #pcr::Update<'a, Types>;
                     pub type UpdateUncheckedQuery<'a> = #pcr::UpdateUnchecked<'a, Types>;
                     pub type UpdateManyQuery<'a> = #pcr::UpdateMany<'a, Types>;
@@ -176,7 +190,7 @@ impl ModelModulePart {
             .map(|(field_name_str, data)| {
                 Module::new(&field_name_str, quote! {
                     use super::super::{_prisma::*, *};
-                    use super::{WhereParam, UniqueWhereParam, WithParam, SetParam, UncheckedSetParam};
+                    use super::{WhereParam, UniqueWhereParam, WithParam, SetParam, UncheckedSetParam, AggregateParam};
 
 					          pub const NAME: &str = #field_name_str;
 
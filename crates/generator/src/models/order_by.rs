@@ -86,24 +86,92 @@ pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
         })
         .unwrap_or_default();
 
-    let (order_by_with_relation_param, relation_field_stuff) = args
+    // the fullTextSearch preview feature renames the input type and adds a _relevance field
+    let order_by_with_relation_input = args
         .dmmf
         .schema
         .find_input_type(&format!("{}OrderByWithRelationInput", model.name()))
+        .or_else(|| {
+            args.dmmf.schema.find_input_type(&format!(
+                "{}OrderByWithRelationAndSearchRelevanceInput",
+                model.name()
+            ))
+        });
+
+    let relevance = args
+        .dmmf
+        .schema
+        .find_input_type(&format!("{}OrderByRelevanceInput", model.name()))
+        .filter(|_| {
+            order_by_with_relation_input
+                .map(|input_type| input_type.fields.iter().any(|f| f.name == "_relevance"))
+                .unwrap_or(false)
+        })
+        .map(|_| {
+            let relevance_field_enum =
+                pascal_ident(&format!("{}OrderByRelevanceFieldEnum", model.name()));
+
+            (
+                quote!(Relevance(Vec<RelevanceField>, String, SortOrder)),
+                quote! {
+                    Self::Relevance(fields, search, sort) => (
+                        "_relevance",
+                        #pcr::PrismaValue::Object(vec![
+                            (
+                                "fields".to_string(),
+                                #pcr::PrismaValue::List(
+                                    fields
+                                        .into_iter()
+                                        .map(|field| #pcr::PrismaValue::Enum(field.to_string()))
+                                        .collect()
+                                )
+                            ),
+                            ("search".to_string(), #pcr::PrismaValue::String(search)),
+                            ("sort".to_string(), sort.into()),
+                        ])
+                    )
+                },
+                quote! {
+                    pub type RelevanceField = #relevance_field_enum;
+
+                    /// Orders records by how relevant the given fields are to `search`.
+                    pub fn relevance(
+                        fields: Vec<RelevanceField>,
+                        search: impl Into<String>,
+                        sort: SortOrder
+                    ) -> OrderByWithRelationParam {
+                        OrderByWithRelationParam::Relevance(fields, search.into(), sort)
+                    }
+                },
+            )
+        });
+
+    let (order_by_with_relation_param, relation_field_stuff) = order_by_with_relation_input
         .map(|input_type| {
             let ((variants, into_pv_arms), field_stuff): ((Vec<_>, Vec<_>), Vec<_>) = input_type
                 .fields
                 .iter()
+                .filter(|field| field.name != "_relevance")
                 .flat_map(|field| {
                     let field_name_str = &field.name;
                     let field_name_pascal = pascal_ident(&field.name);
 
                     let typ_ref = &field.input_types[0];
-                    let typ = typ_ref.to_tokens(
-                        &quote!(super::),
-                        &FieldArity::Required,
-                        &args.schema.db,
-                    )?;
+                    // nullable scalar fields also accept { sort, nulls }
+                    let nullable_sort = field
+                        .input_types
+                        .iter()
+                        .any(|input_type| input_type.typ == "SortOrderInput");
+
+                    let typ = if nullable_sort {
+                        quote!(super::SortOrderInput)
+                    } else {
+                        typ_ref.to_tokens(
+                            &quote!(super::),
+                            &FieldArity::Required,
+                            &args.schema.db,
+                        )?
+                    };
 
                     let pv = match &typ_ref.location {
                         TypeLocation::EnumTypes | TypeLocation::Scalar => quote!(param.into()),
@@ -128,11 +196,15 @@ pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
                         (
                             field_name_str,
                             (
-                                typ_ref.to_tokens(
-                                    &quote!(),
-                                    &FieldArity::Required,
-                                    &args.schema.db,
-                                )?,
+                                if nullable_sort {
+                                    quote!(SortOrderInput)
+                                } else {
+                                    typ_ref.to_tokens(
+                                        &quote!(),
+                                        &FieldArity::Required,
+                                        &args.schema.db,
+                                    )?
+                                },
                                 quote! {
                                     impl From<Order> for super::OrderByWithRelationParam {
                                         fn from(Order(v): Order) -> Self {
@@ -146,8 +218,20 @@ pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
                 })
                 .unzip();
 
+            let (relevance_variant, relevance_arm, relevance_fn) = match relevance {
+                Some((variant, arm, relevance_fn)) => {
+                    (Some(variant), Some(arm), Some(relevance_fn))
+                }
+                None => (None, None, None),
+            };
+
+            let variants = variants.into_iter().chain(relevance_variant);
+            let into_pv_arms = into_pv_arms.into_iter().chain(relevance_arm);
+
             (
                 quote! {
+                    #relevance_fn
+
                     #[derive(Debug, Clone)]
                     pub enum OrderByWithRelationParam {
                        #(#variants),*
@@ -190,14 +274,27 @@ pub fn model_data(model: ModelWalker, args: &GenerateArgs) -> ModelModulePart {
 
                 let impls = data.iter().map(|(_, impls)| impls);
 
+                // allows passing a plain SortOrder as well as eg. SortOrder::Asc.nulls_last()
+                let order_fn = if typ.to_string() == quote!(SortOrderInput).to_string() {
+                    quote! {
+                        pub fn order<T: From<Order>>(v: impl Into<#typ>) -> T {
+                            Order(v.into()).into()
+                        }
+                    }
+                } else {
+                    quote! {
+                        pub fn order<T: From<Order>>(v: #typ) -> T {
+                            Order(v).into()
+                        }
+                    }
+                };
+
                 (
                     name,
                     quote! {
                         pub struct Order(#typ);
 
-                        pub fn order<T: From<Order>>(v: #typ) -> T {
-                            Order(v).into()
-                        }
+                        #order_fn
 
                         #(#impls)*
                     },
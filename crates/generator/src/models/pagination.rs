@@ -16,5 +16,10 @@ pub fn fetch_builder_fns(model_name_snake: &Ident) -> TokenStream {
             self.0 = self.0.cursor(value.into());
             self
         }
+
+        pub fn distinct(mut self, value: Vec<#model_name_snake::ScalarField>) -> Self {
+            self.0 = self.0.distinct(value);
+            self
+        }
     }
 }
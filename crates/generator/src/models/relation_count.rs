@@ -0,0 +1,94 @@
+use prisma_client_rust_sdk::prisma::prisma_models::walkers::{ModelWalker, RelationFieldWalker};
+
+use crate::prelude::*;
+
+use super::ModelModulePart;
+
+/// Relations that can be counted via `_count`, which the engine only supports for list relations.
+pub fn countable_relations<'a>(
+    model: ModelWalker<'a>,
+) -> impl Iterator<Item = RelationFieldWalker<'a>> {
+    model
+        .relation_fields()
+        .filter(|field| field.ast_field().arity.is_list())
+}
+
+pub fn model_data(model: ModelWalker) -> ModelModulePart {
+    let pcr = quote!(::prisma_client_rust);
+
+    let mut struct_fields = vec![];
+
+    let (variants, (into_arms, fields)): (Vec<_>, (Vec<_>, _)) = countable_relations(model)
+        .map(|field| {
+            let field_name_str = field.name();
+            let field_name_snake = snake_ident(field.name());
+            let field_name_pascal = pascal_ident(field.name());
+            let relation_model_name_snake = snake_ident(field.related_model().name());
+
+            let variant = quote!(#field_name_pascal(Vec<#relation_model_name_snake::WhereParam>));
+
+            let into_arm = quote! {
+                Self::#field_name_pascal(where_params) => (
+                    #field_name_snake::NAME,
+                    where_params
+                        .into_iter()
+                        .map(#pcr::WhereInput::serialize)
+                        .collect()
+                )
+            };
+
+            let field_fn = quote! {
+                pub fn count(
+                    where_params: Vec<#relation_model_name_snake::WhereParam>
+                ) -> super::RelationCountParam {
+                    super::RelationCountParam::#field_name_pascal(where_params)
+                }
+            };
+
+            struct_fields.push(quote! {
+                #[serde(rename = #field_name_str, default, skip_serializing_if = "Option::is_none")]
+                pub #field_name_snake: Option<i64>
+            });
+
+            (variant, (into_arm, (field.name().to_string(), field_fn)))
+        })
+        .unzip();
+
+    if variants.is_empty() {
+        return ModelModulePart {
+            data: quote!(),
+            fields,
+        };
+    }
+
+    ModelModulePart {
+        data: quote! {
+            #[derive(Debug, Clone)]
+            pub enum RelationCountParam {
+                #(#variants),*
+            }
+
+            impl Into<(&'static str, Vec<#pcr::SerializedWhereInput>)> for RelationCountParam {
+                fn into(self) -> (&'static str, Vec<#pcr::SerializedWhereInput>) {
+                    match self {
+                        #(#into_arms),*
+                    }
+                }
+            }
+
+            /// Counts the records of list relations, only including records that match each relation's filters.
+            /// The counts are stored in `Data::_count`.
+            pub fn relation_counts(params: Vec<RelationCountParam>) -> WithParam {
+                WithParam::RelationCount(params)
+            }
+
+            /// The relation counts requested with `relation_counts`.
+            /// Relations that weren't counted are `None`.
+            #[derive(Debug, Clone, Default, ::serde::Serialize, ::serde::Deserialize)]
+            pub struct RelationCount {
+                #(#struct_fields),*
+            }
+        },
+        fields,
+    }
+}
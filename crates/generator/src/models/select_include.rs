@@ -7,6 +7,8 @@ use syn::{parse_quote, ItemStruct};
 
 use crate::prelude::*;
 
+use super::relation_count;
+
 fn model_macro<'a>(
     model: ModelWalker<'a>,
     module_path: &TokenStream,
@@ -205,15 +207,30 @@ fn model_module_enum(model: ModelWalker, variant: Variant) -> TokenStream {
 
     let variant_param = variant.param();
 
+    let (relation_count_variant, relation_count_arm) =
+        match relation_count::countable_relations(model).next() {
+            Some(_) => (
+                Some(quote!(RelationCount(Vec<RelationCountParam>))),
+                Some(quote! {
+                    Self::RelationCount(params) => #pcr::relation_count_selection(
+                        params.into_iter().map(Into::into)
+                    )
+                }),
+            ),
+            None => (None, None),
+        };
+
     quote! {
         pub enum #variant_param {
-            #(#variants),*
+            #(#variants,)*
+            #relation_count_variant
         }
 
         impl Into<#pcr::Selection> for #variant_param {
             fn into(self) -> #pcr::Selection {
                 match self {
-                    #(Self::#field_names_pascal(data) => data.into()),*
+                    #(Self::#field_names_pascal(data) => data.into(),)*
+                    #relation_count_arm
                 }
             }
         }
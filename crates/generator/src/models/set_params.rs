@@ -2,7 +2,10 @@ use std::collections::BTreeMap;
 
 use prisma_client_rust_sdk::prisma::{
     prisma_models::{
-        walkers::{FieldWalker, ModelWalker, RefinedFieldWalker, RelationFieldWalker},
+        walkers::{
+            FieldWalker, ModelWalker, RefinedFieldWalker, RefinedRelationWalker,
+            RelationFieldWalker,
+        },
         FieldArity,
     },
     psl::parser_database::ScalarFieldType,
@@ -10,7 +13,7 @@ use prisma_client_rust_sdk::prisma::{
 
 use crate::{prelude::*, write_params};
 
-use super::ModelModulePart;
+use super::{required_fields, ModelModulePart};
 
 pub struct RelationSetParamConfig {
     pub action: &'static str,
@@ -55,6 +58,482 @@ fn relation_field_set_params(field: RelationFieldWalker) -> Vec<RelationSetParam
     }
 }
 
+struct NestedWriteParam {
+    action: &'static str,
+    definition: TokenStream,
+    pattern: TokenStream,
+    value: TokenStream,
+    function: TokenStream,
+}
+
+/// Generates nested writes for a relation field, allowing related records to be
+/// created, updated and deleted in the same operation as the parent record.
+fn nested_write_params(
+    field: RelationFieldWalker,
+) -> (Vec<TokenStream>, Vec<TokenStream>, TokenStream) {
+    let pcr = quote!(::prisma_client_rust);
+
+    let related_model = field.related_model();
+    let opposite_field_name = field.opposite_relation_field().map(|f| f.name());
+
+    // The relation back to the parent is provided by the engine,
+    // so only the related model's other required fields are needed
+    let Some(required_fields) = required_fields(related_model, &quote!()) else {
+        return Default::default();
+    };
+
+    let field_name_snake = snake_ident(field.name());
+    let field_name_pascal = pascal_ident(field.name());
+    let related_model_name_snake = snake_ident(related_model.name());
+
+    let (names, (types, push_wrappers)): (Vec<_>, (Vec<_>, Vec<_>)) = required_fields
+        .into_iter()
+        .filter(|f| Some(f.inner.name()) != opposite_field_name)
+        .map(|f| (snake_ident(f.inner.name()), (f.typ, f.push_wrapper)))
+        .unzip();
+
+    let create_pv = |var: TokenStream| {
+        quote! {
+            #pcr::PrismaValue::Object(#pcr::merge_fields(
+                #var.to_params().into_iter().map(Into::into).collect()
+            ))
+        }
+    };
+    let set_params_pv = |var: TokenStream| {
+        quote! {
+            #pcr::PrismaValue::Object(#pcr::merge_fields(
+                #var.into_iter().map(Into::into).collect()
+            ))
+        }
+    };
+    let unique_where_pv = |var: TokenStream| {
+        quote! {
+            #pcr::PrismaValue::Object(
+                [#var]
+                    .into_iter()
+                    .map(#pcr::WhereInput::serialize)
+                    .map(#pcr::SerializedWhereInput::transform_equals)
+                    .collect()
+            )
+        }
+    };
+    let where_pv = |var: TokenStream| {
+        quote! {
+            #pcr::PrismaValue::Object(#pcr::merge_fields(
+                #var.into_iter().map(#pcr::WhereInput::serialize).map(Into::into).collect()
+            ))
+        }
+    };
+
+    let create_type = quote!(#field_name_snake::Create);
+    let related_unique_where = quote!(super::#related_model_name_snake::UniqueWhereParam);
+    let related_where = quote!(super::#related_model_name_snake::WhereParam);
+    let related_set = quote!(super::#related_model_name_snake::SetParam);
+
+    let variant = |action: &str| format_ident!("{}{field_name_pascal}", pascal_ident(action));
+
+    let create_value = create_pv(quote!(create));
+    let update_value = set_params_pv(quote!(update));
+    let unique_where_value = unique_where_pv(quote!(_where));
+
+    let params = match field.ast_field().arity {
+        FieldArity::List => {
+            let create_variant = variant("create");
+            let create_many_variant = variant("createMany");
+            let connect_or_create_variant = variant("connectOrCreate");
+            let upsert_variant = variant("upsert");
+            let update_variant = variant("update");
+            let update_many_variant = variant("updateMany");
+            let delete_variant = variant("delete");
+            let delete_many_variant = variant("deleteMany");
+
+            let many_where_value = where_pv(quote!(_where));
+
+            // createMany is only available for one-to-many relations, and since the engine's
+            // create many inputs only accept scalars it gets its own input type
+            let create_many = matches!(field.relation().refine(), RefinedRelationWalker::Inline(_))
+                .then(|| create_many_input(field))
+                .flatten()
+                .map(|create_many_input| NestedWriteParam {
+                    action: "createMany",
+                    definition: quote!(#create_many_variant(Vec<#field_name_snake::CreateMany>)),
+                    pattern: quote!(#create_many_variant(creates)),
+                    value: quote! {
+                        #pcr::PrismaValue::Object(vec![(
+                            "data".to_string(),
+                            #pcr::PrismaValue::List(
+                                creates.into_iter().map(|create| #create_value).collect()
+                            )
+                        )])
+                    },
+                    function: quote! {
+                        #create_many_input
+
+                        pub fn create_many(creates: Vec<CreateMany>) -> SetParam {
+                            SetParam::#create_many_variant(creates)
+                        }
+                    },
+                });
+
+            [
+                Some(NestedWriteParam {
+                    action: "create",
+                    definition: quote!(#create_variant(Vec<#create_type>)),
+                    pattern: quote!(#create_variant(creates)),
+                    value: quote! {
+                        #pcr::PrismaValue::List(
+                            creates.into_iter().map(|create| #create_value).collect()
+                        )
+                    },
+                    function: quote! {
+                        pub fn create(creates: Vec<Create>) -> SetParam {
+                            SetParam::#create_variant(creates)
+                        }
+                    },
+                }),
+                create_many,
+                Some(NestedWriteParam {
+                    action: "connectOrCreate",
+                    definition: quote! {
+                        #connect_or_create_variant(Vec<(#related_unique_where, #create_type)>)
+                    },
+                    pattern: quote!(#connect_or_create_variant(params)),
+                    value: quote! {
+                        #pcr::PrismaValue::List(
+                            params
+                                .into_iter()
+                                .map(|(_where, create)| #pcr::PrismaValue::Object(vec![
+                                    ("where".to_string(), #unique_where_value),
+                                    ("create".to_string(), #create_value)
+                                ]))
+                                .collect()
+                        )
+                    },
+                    function: quote! {
+                        pub fn connect_or_create(
+                            params: Vec<(#related_model_name_snake::UniqueWhereParam, Create)>
+                        ) -> SetParam {
+                            SetParam::#connect_or_create_variant(params)
+                        }
+                    },
+                }),
+                Some(NestedWriteParam {
+                    action: "upsert",
+                    definition: quote! {
+                        #upsert_variant(Vec<(#related_unique_where, #create_type, Vec<#related_set>)>)
+                    },
+                    pattern: quote!(#upsert_variant(params)),
+                    value: quote! {
+                        #pcr::PrismaValue::List(
+                            params
+                                .into_iter()
+                                .map(|(_where, create, update)| #pcr::PrismaValue::Object(vec![
+                                    ("where".to_string(), #unique_where_value),
+                                    ("create".to_string(), #create_value),
+                                    ("update".to_string(), #update_value)
+                                ]))
+                                .collect()
+                        )
+                    },
+                    function: quote! {
+                        pub fn upsert(
+                            params: Vec<(
+                                #related_model_name_snake::UniqueWhereParam,
+                                Create,
+                                Vec<#related_model_name_snake::SetParam>
+                            )>
+                        ) -> SetParam {
+                            SetParam::#upsert_variant(params)
+                        }
+                    },
+                }),
+                Some(NestedWriteParam {
+                    action: "update",
+                    definition: quote! {
+                        #update_variant(Vec<(#related_unique_where, Vec<#related_set>)>)
+                    },
+                    pattern: quote!(#update_variant(params)),
+                    value: quote! {
+                        #pcr::PrismaValue::List(
+                            params
+                                .into_iter()
+                                .map(|(_where, update)| #pcr::PrismaValue::Object(vec![
+                                    ("where".to_string(), #unique_where_value),
+                                    ("data".to_string(), #update_value)
+                                ]))
+                                .collect()
+                        )
+                    },
+                    function: quote! {
+                        pub fn update(
+                            params: Vec<(
+                                #related_model_name_snake::UniqueWhereParam,
+                                Vec<#related_model_name_snake::SetParam>
+                            )>
+                        ) -> SetParam {
+                            SetParam::#update_variant(params)
+                        }
+                    },
+                }),
+                Some(NestedWriteParam {
+                    action: "updateMany",
+                    definition: quote!(#update_many_variant(Vec<#related_where>, Vec<#related_set>)),
+                    pattern: quote!(#update_many_variant(_where, update)),
+                    value: quote! {
+                        #pcr::PrismaValue::Object(vec![
+                            ("where".to_string(), #many_where_value),
+                            ("data".to_string(), #update_value)
+                        ])
+                    },
+                    function: quote! {
+                        pub fn update_many(
+                            _where: Vec<#related_model_name_snake::WhereParam>,
+                            update: Vec<#related_model_name_snake::SetParam>
+                        ) -> SetParam {
+                            SetParam::#update_many_variant(_where, update)
+                        }
+                    },
+                }),
+                Some(NestedWriteParam {
+                    action: "delete",
+                    definition: quote!(#delete_variant(Vec<#related_unique_where>)),
+                    pattern: quote!(#delete_variant(where_params)),
+                    value: quote! {
+                        #pcr::PrismaValue::List(
+                            where_params
+                                .into_iter()
+                                .map(#pcr::WhereInput::serialize)
+                                .map(#pcr::SerializedWhereInput::transform_equals)
+                                .map(|v| #pcr::PrismaValue::Object(vec![v]))
+                                .collect()
+                        )
+                    },
+                    function: quote! {
+                        pub fn delete(params: Vec<#related_model_name_snake::UniqueWhereParam>) -> SetParam {
+                            SetParam::#delete_variant(params)
+                        }
+                    },
+                }),
+                Some(NestedWriteParam {
+                    action: "deleteMany",
+                    definition: quote!(#delete_many_variant(Vec<#related_where>)),
+                    pattern: quote!(#delete_many_variant(_where)),
+                    value: many_where_value,
+                    function: quote! {
+                        pub fn delete_many(_where: Vec<#related_model_name_snake::WhereParam>) -> SetParam {
+                            SetParam::#delete_many_variant(_where)
+                        }
+                    },
+                }),
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+        }
+        arity => {
+            let create_variant = variant("create");
+            let connect_or_create_variant = variant("connectOrCreate");
+            let upsert_variant = variant("upsert");
+            let update_variant = variant("update");
+            let delete_variant = variant("delete");
+
+            // Required relations can't be deleted without deleting the parent
+            let delete = arity.is_optional().then(|| NestedWriteParam {
+                action: "delete",
+                definition: quote!(#delete_variant),
+                pattern: quote!(#delete_variant),
+                value: quote!(#pcr::PrismaValue::Boolean(true)),
+                function: quote! {
+                    pub fn delete() -> SetParam {
+                        SetParam::#delete_variant
+                    }
+                },
+            });
+
+            [
+                Some(NestedWriteParam {
+                    action: "create",
+                    definition: quote!(#create_variant(#create_type)),
+                    pattern: quote!(#create_variant(create)),
+                    value: create_value.clone(),
+                    function: quote! {
+                        pub fn create(create: Create) -> SetParam {
+                            SetParam::#create_variant(create)
+                        }
+                    },
+                }),
+                Some(NestedWriteParam {
+                    action: "connectOrCreate",
+                    definition: quote!(#connect_or_create_variant(#related_unique_where, #create_type)),
+                    pattern: quote!(#connect_or_create_variant(_where, create)),
+                    value: quote! {
+                        #pcr::PrismaValue::Object(vec![
+                            ("where".to_string(), #unique_where_value),
+                            ("create".to_string(), #create_value)
+                        ])
+                    },
+                    function: quote! {
+                        pub fn connect_or_create(
+                            _where: #related_model_name_snake::UniqueWhereParam,
+                            create: Create
+                        ) -> SetParam {
+                            SetParam::#connect_or_create_variant(_where, create)
+                        }
+                    },
+                }),
+                Some(NestedWriteParam {
+                    action: "upsert",
+                    definition: quote!(#upsert_variant(#create_type, Vec<#related_set>)),
+                    pattern: quote!(#upsert_variant(create, update)),
+                    value: quote! {
+                        #pcr::PrismaValue::Object(vec![
+                            ("create".to_string(), #create_value),
+                            ("update".to_string(), #update_value)
+                        ])
+                    },
+                    function: quote! {
+                        pub fn upsert(
+                            create: Create,
+                            update: Vec<#related_model_name_snake::SetParam>
+                        ) -> SetParam {
+                            SetParam::#upsert_variant(create, update)
+                        }
+                    },
+                }),
+                Some(NestedWriteParam {
+                    action: "update",
+                    definition: quote!(#update_variant(Vec<#related_set>)),
+                    pattern: quote!(#update_variant(update)),
+                    value: update_value.clone(),
+                    function: quote! {
+                        pub fn update(params: Vec<#related_model_name_snake::SetParam>) -> SetParam {
+                            SetParam::#update_variant(params)
+                        }
+                    },
+                }),
+                delete,
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+        }
+    };
+
+    let (variants, match_arms): (Vec<_>, Vec<_>) = params
+        .iter()
+        .map(|param| {
+            let NestedWriteParam {
+                action,
+                definition,
+                pattern,
+                value,
+                ..
+            } = param;
+
+            (
+                definition.clone(),
+                quote! {
+                    Self::#pattern => (
+                        #field_name_snake::NAME,
+                        #pcr::PrismaValue::Object(vec![(#action.to_string(), #value)])
+                    )
+                },
+            )
+        })
+        .unzip();
+
+    let functions = params.iter().map(|param| &param.function);
+
+    let field_fns = quote! {
+        #[derive(Debug, Clone)]
+        pub struct Create {
+            #(pub #names: #types,)*
+            pub _params: Vec<#related_model_name_snake::SetParam>
+        }
+
+        impl Create {
+            pub fn new(#(#names: #types,)* _params: Vec<#related_model_name_snake::SetParam>) -> Self {
+                Self {
+                    #(#names,)*
+                    _params
+                }
+            }
+
+            pub fn to_params(mut self) -> Vec<#related_model_name_snake::SetParam> {
+                self._params.extend([
+                    #(#related_model_name_snake::#names::#push_wrappers(self.#names)),*
+                ]);
+
+                self._params
+            }
+        }
+
+        #(#functions)*
+    };
+
+    (variants, match_arms, field_fns)
+}
+
+/// Generates the input for a nested `createMany`, which takes the related model's
+/// scalar fields (including foreign keys) minus those pointing back to the parent.
+fn create_many_input(field: RelationFieldWalker) -> Option<TokenStream> {
+    let related_model = field.related_model();
+    let related_model_name_snake = snake_ident(related_model.name());
+
+    let back_relation_fields = field
+        .opposite_relation_field()
+        .and_then(|f| f.referencing_fields())
+        .into_iter()
+        .flatten()
+        .map(|f| f.field_id())
+        .collect::<Vec<_>>();
+
+    let (names, types): (Vec<_>, Vec<_>) = related_model
+        .scalar_fields()
+        .filter(|f| f.required_on_create() && !back_relation_fields.contains(&f.field_id()))
+        .map(|field| {
+            Some((
+                snake_ident(field.name()),
+                match field.scalar_field_type() {
+                    ScalarFieldType::CompositeType(id) => {
+                        let comp_type_snake = snake_ident(related_model.db.walk(id).name());
+
+                        quote!(#comp_type_snake::Create)
+                    }
+                    _ => field.type_tokens(&quote!())?,
+                },
+            ))
+        })
+        .collect::<Option<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+
+    Some(quote! {
+        #[derive(Debug, Clone)]
+        pub struct CreateMany {
+            #(pub #names: #types,)*
+            pub _params: Vec<#related_model_name_snake::UncheckedSetParam>
+        }
+
+        impl CreateMany {
+            pub fn new(#(#names: #types,)* _params: Vec<#related_model_name_snake::UncheckedSetParam>) -> Self {
+                Self {
+                    #(#names,)*
+                    _params
+                }
+            }
+
+            pub fn to_params(mut self) -> Vec<#related_model_name_snake::UncheckedSetParam> {
+                self._params.extend([
+                    #(#related_model_name_snake::#names::set(self.#names)),*
+                ]);
+
+                self._params
+            }
+        }
+    })
+}
+
 fn field_set_params(
     field: FieldWalker,
     args: &GenerateArgs,
@@ -572,10 +1051,21 @@ fn field_set_params(
                 }
             };
 
+            let (nested_variants, nested_functions, nested_fns) =
+                nested_write_params(relation_field);
+
             variants.extend(v);
+            variants.extend(nested_variants);
             functions.extend(f);
-
-            (field.name().to_string(), base)
+            functions.extend(nested_functions);
+
+            (
+                field.name().to_string(),
+                quote! {
+                    #base
+                    #nested_fns
+                },
+            )
         }
     };
 
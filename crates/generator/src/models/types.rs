@@ -4,6 +4,8 @@ use prisma_client_rust_sdk::prisma::{
 
 use crate::prelude::*;
 
+use super::where_params::unique_field_combos;
+
 fn scalar_selections_fn(model: ModelWalker, module_path: &TokenStream) -> TokenStream {
     let pcr = quote!(::prisma_client_rust);
 
@@ -34,12 +36,85 @@ fn scalar_selections_fn(model: ModelWalker, module_path: &TokenStream) -> TokenS
     }
 }
 
+fn scalar_field_enum(model: ModelWalker) -> TokenStream {
+    let (variants, names): (Vec<_>, Vec<_>) = model
+        .scalar_fields()
+        .filter(|field| {
+            !matches!(
+                field.scalar_field_type(),
+                ScalarFieldType::CompositeType(_) | ScalarFieldType::Unsupported(_)
+            )
+        })
+        .map(|field| (pascal_ident(field.name()), snake_ident(field.name())))
+        .unzip();
+
+    quote! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum ScalarField {
+            #(#variants),*
+        }
+
+        impl ToString for ScalarField {
+            fn to_string(&self) -> String {
+                match self {
+                    #(Self::#variants => #names::NAME),*
+                }
+                .to_string()
+            }
+        }
+    }
+}
+
 pub fn r#struct(model: ModelWalker, module_path: &TokenStream) -> TokenStream {
     let pcr = quote!(::prisma_client_rust);
 
     let scalar_selections_fn = scalar_selections_fn(model, module_path);
+    let scalar_field_enum = scalar_field_enum(model);
+
+    let primary_key = {
+        let combos = unique_field_combos(model);
+
+        match model.primary_key() {
+            Some(primary_key) => combos
+                .into_iter()
+                .find(|fields| primary_key.contains_exactly_fields(fields.clone().into_iter())),
+            None => combos.into_iter().find(|fields| {
+                fields
+                    .iter()
+                    .all(|field| field.ast_field().arity.is_required())
+            }),
+        }
+        .unwrap_or_default()
+    };
+
+    let primary_key_names = primary_key.iter().map(|field| snake_ident(field.name()));
+
+    // models without a primary key or unique fields can't be paginated with a cursor
+    let primary_key_types = (!primary_key.is_empty()).then(|| {
+        let variant_name = format_ident!(
+            "{}Equals",
+            primary_key
+                .iter()
+                .map(|field| pascal_ident(field.name()).to_string())
+                .collect::<String>()
+        );
+        let values = primary_key.iter().map(|field| {
+            let field_name_snake = snake_ident(field.name());
+            quote!(data.#field_name_snake.clone())
+        });
+
+        quote! {
+            impl #pcr::PrimaryKeyTypes for Types {
+                fn primary_key_cursor(data: &Self::Data) -> Self::Cursor {
+                    UniqueWhereParam::#variant_name(#(#values),*)
+                }
+            }
+        }
+    });
 
     quote! {
+        #scalar_field_enum
+
         #[derive(Debug, Clone)]
         pub struct Types;
 
@@ -52,10 +127,19 @@ pub fn r#struct(model: ModelWalker, module_path: &TokenStream) -> TokenStream {
             type With = WithParam;
             type OrderBy = OrderByWithRelationParam;
             type Cursor = UniqueWhereParam;
+            type Aggregate = AggregateParam;
+            type AggregateResult = AggregateResult;
+            type ScalarField = ScalarField;
+            type Having = HavingParam;
+            type OrderByWithAggregation = OrderByWithAggregationParam;
+            type GroupByResult = GroupByResult;
 
             const MODEL: &'static str = NAME;
+            const PRIMARY_KEY: &'static [&'static str] = &[#(#primary_key_names::NAME),*];
 
             #scalar_selections_fn
         }
+
+        #primary_key_types
     }
 }
@@ -5,7 +5,7 @@ use prisma_client_rust_sdk::prisma::prisma_models::{
 
 use crate::prelude::*;
 
-use super::{order_by, pagination, ModelModulePart};
+use super::{order_by, pagination, relation_count, ModelModulePart};
 
 pub fn builder_fn(field: RelationFieldWalker) -> TokenStream {
     let relation_model_name_snake = snake_ident(field.related_model().name());
@@ -133,17 +133,32 @@ pub fn model_data(model: ModelWalker) -> ModelModulePart {
         })
         .collect();
 
+    let (relation_count_variant, relation_count_arm) =
+        match relation_count::countable_relations(model).next() {
+            Some(_) => (
+                Some(quote!(RelationCount(Vec<RelationCountParam>))),
+                Some(quote! {
+                    Self::RelationCount(params) => ::prisma_client_rust::relation_count_selection(
+                        params.into_iter().map(Into::into)
+                    )
+                }),
+            ),
+            None => (None, None),
+        };
+
     ModelModulePart {
         data: quote! {
             #[derive(Debug, Clone)]
             pub enum WithParam {
-                #(#variants),*
+                #(#variants,)*
+                #relation_count_variant
             }
 
             impl Into<::prisma_client_rust::Selection> for WithParam {
                 fn into(self) -> ::prisma_client_rust::Selection {
                     match self {
-                        #(#into_selection_arms),*
+                        #(#into_selection_arms,)*
+                        #relation_count_arm
                     }
                 }
             }
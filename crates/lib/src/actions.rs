@@ -1,16 +1,76 @@
-use crate::{ModelWriteOperation, SerializedWhereInput};
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use futures::future::BoxFuture;
+use serde::{de::DeserializeOwned, de::IntoDeserializer, Deserialize};
+
+use crate::{BatchResult, ModelWriteOperation, SerializedWhereInput};
 
 pub trait WhereInput {
     fn serialize(self) -> SerializedWhereInput;
 }
 
-#[derive(Debug, PartialEq, Eq)]
+/// The result of a write, as returned by the query engine.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModelMutationResult {
+    /// The record returned by a create, update, upsert or delete.
+    /// It can be deserialized into the model's `Data` type using [`ModelMutationCallbackData::record`].
+    Record(serde_value::Value),
+    /// The number of records affected by a `create_many`, `update_many` or `delete_many`.
+    Count(i64),
+}
+
+impl ModelMutationResult {
+    pub(crate) fn new(action: ModelWriteOperation, value: serde_value::Value) -> Self {
+        match action {
+            ModelWriteOperation::CreateMany
+            | ModelWriteOperation::UpdateMany
+            | ModelWriteOperation::DeleteMany => {
+                match BatchResult::deserialize(value.clone().into_deserializer()) {
+                    Ok(result) => Self::Count(result.count),
+                    Err(_) => Self::Record(value),
+                }
+            }
+            _ => Self::Record(value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct ModelMutationCallbackData {
     pub action: ModelWriteOperation,
-    pub model: &'static str,
+    pub model: String,
+    pub result: ModelMutationResult,
+}
+
+impl ModelMutationCallbackData {
+    /// Deserializes the record returned by the write,
+    /// or returns `None` if the write affected many records.
+    pub fn record<T: DeserializeOwned>(&self) -> Option<Result<T, serde_value::DeserializerError>> {
+        match &self.result {
+            ModelMutationResult::Record(value) => Some(T::deserialize(value.clone())),
+            ModelMutationResult::Count(_) => None,
+        }
+    }
+
+    /// The number of records affected by the write.
+    pub fn count(&self) -> i64 {
+        match &self.result {
+            ModelMutationResult::Record(_) => 1,
+            ModelMutationResult::Count(count) => *count,
+        }
+    }
 }
 
-pub type ModelMutationCallback = Box<dyn Fn(ModelMutationCallbackData) + Sync + Send>;
+pub enum ModelMutationCallback {
+    Sync(Box<dyn Fn(ModelMutationCallbackData) + Sync + Send>),
+    Async(Box<dyn Fn(ModelMutationCallbackData) -> BoxFuture<'static, ()> + Sync + Send>),
+}
+
+/// Mutations made inside a transaction, which are only notified once it commits.
+pub(crate) type PendingMutations = Arc<Mutex<Vec<ModelMutationCallbackData>>>;
 
 pub struct ActionNotifier {
     pub model_mutation_callbacks: Vec<ModelMutationCallback>,
@@ -22,6 +82,38 @@ impl ActionNotifier {
             model_mutation_callbacks: vec![],
         }
     }
+
+    pub fn add_callback(
+        &mut self,
+        callback: impl Fn(ModelMutationCallbackData) + 'static + Send + Sync,
+    ) {
+        self.model_mutation_callbacks
+            .push(ModelMutationCallback::Sync(Box::new(callback)));
+    }
+
+    pub fn add_async_callback<Fut>(
+        &mut self,
+        callback: impl Fn(ModelMutationCallbackData) -> Fut + 'static + Send + Sync,
+    ) where
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.model_mutation_callbacks
+            .push(ModelMutationCallback::Async(Box::new(move |data| {
+                Box::pin(callback(data))
+            })));
+    }
+
+    /// Runs every callback in the order they were added, waiting for async callbacks to finish.
+    pub(crate) async fn notify(&self, mutations: Vec<ModelMutationCallbackData>) {
+        for data in mutations {
+            for callback in &self.model_mutation_callbacks {
+                match callback {
+                    ModelMutationCallback::Sync(callback) => callback(data.clone()),
+                    ModelMutationCallback::Async(callback) => callback(data.clone()).await,
+                }
+            }
+        }
+    }
 }
 
 impl Default for ActionNotifier {
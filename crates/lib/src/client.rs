@@ -1,15 +1,36 @@
-use crate::ActionNotifier;
+use crate::actions::PendingMutations;
+use crate::connection::{redact_url, ConnectionState};
+use crate::instrumentation::{row_count, QueryInstrument};
+use crate::metrics::{query_key, MetricsRecorder};
+use crate::middleware::MiddlewareStack;
+use crate::transaction::TransactionHooks;
+use crate::{
+    ActionNotifier, ConnectionConfig, ConnectionOptions, Metrics, Middleware,
+    ModelMutationCallbackData, ModelMutationResult, ModelWriteOperation, Next, NextBatch,
+    QueryContext,
+};
+use prisma_models::PrismaValue;
 use psl::Diagnostics;
 use query_core::{
     protocol::EngineProtocol,
     schema::{self, QuerySchema},
-    BatchDocumentTransaction, CoreError, Operation, TxId,
+    BatchDocumentTransaction, CoreError, Operation, Selection, TransactionOptions, TxId,
 };
 
-use std::sync::Arc;
+use std::{
+    future::Future,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 use thiserror::Error;
+use tokio::sync::{RwLock, RwLockReadGuard};
+use tracing::Instrument;
 
-use crate::{prisma_value, QueryError, Result};
+use crate::{prisma_value, Query, QueryError, QueryRaw, Raw, Result, RunCommandRaw};
 
 pub type Executor = Box<dyn query_core::QueryExecutor + Send + Sync + 'static>;
 
@@ -20,15 +41,326 @@ pub trait PrismaClient {
 }
 
 pub struct ExecutorConnector {
-    pub executor: Executor,
+    /// `None` if loading it failed when a lazy client was built, or after the client is disconnected.
+    executor: RwLock<Option<Executor>>,
+    closed: AtomicBool,
+    datamodel: Arc<psl::ValidatedSchema>,
     pub query_schema: Arc<QuerySchema>,
     pub url: String,
+    pub provider: &'static str,
+    pub options: ConnectionOptions,
+}
+
+impl ExecutorConnector {
+    /// The connector's executor, loading it if that failed when the client was built.
+    async fn executor(&self) -> Result<RwLockReadGuard<'_, Executor>> {
+        if let Ok(executor) = RwLockReadGuard::try_map(self.executor.read().await, Option::as_ref) {
+            return Ok(executor);
+        }
+
+        let mut executor = self.executor.write().await;
+
+        if self.closed.load(Ordering::SeqCst) {
+            return Err(QueryError::Disconnected);
+        }
+
+        // another query may have loaded it while waiting for the lock
+        if executor.is_none() {
+            *executor = Some(
+                load_executor(&self.datamodel, &self.url)
+                    .await
+                    .map_err(QueryError::from_engine)?,
+            );
+        }
+
+        Ok(RwLockReadGuard::map(executor.downgrade(), |executor| {
+            executor.as_ref().unwrap()
+        }))
+    }
+
+    /// Establishes a connection to the database if the pool doesn't have one.
+    async fn connect(&self) -> Result<()> {
+        let executor = self.executor().await?;
+
+        executor
+            .primary_connector()
+            .get_connection()
+            .await
+            .map_err(|e| QueryError::from_engine(CoreError::from(e)))?;
+
+        self.set_journal_mode(&executor)
+            .await
+            .map_err(QueryError::from_engine)
+    }
+
+    /// Sets the SQLite journal mode if one was configured.
+    /// It's stored in the database file, so setting it on one connection is enough.
+    async fn set_journal_mode(&self, executor: &Executor) -> std::result::Result<(), CoreError> {
+        let journal_mode = match self.options.sqlite_journal_mode {
+            Some(journal_mode) => journal_mode,
+            None => return Ok(()),
+        };
+
+        // the pragma returns the new mode, so it must be a query rather than an execute
+        let op = Operation::Write(Selection::new(
+            "queryRaw".to_string(),
+            None,
+            [
+                (
+                    "query".to_string(),
+                    PrismaValue::String(format!("PRAGMA journal_mode = {}", journal_mode.as_str()))
+                        .into(),
+                ),
+                (
+                    "parameters".to_string(),
+                    PrismaValue::String("[]".to_string()).into(),
+                ),
+            ],
+            [],
+        ));
+
+        executor
+            .execute(
+                None,
+                op,
+                self.query_schema.clone(),
+                None,
+                EngineProtocol::Json,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn execute(&self, tx_id: Option<TxId>, op: Operation) -> Result<serde_value::Value> {
+        let response = self
+            .executor()
+            .await?
+            .execute(
+                tx_id,
+                op,
+                self.query_schema.clone(),
+                None,
+                EngineProtocol::Json,
+            )
+            .await
+            .map_err(QueryError::from_engine)?;
+
+        let data: prisma_value::Item = response.data.into();
+
+        let data = serde_value::to_value(data)
+            .map_err(|e| e.to_string())
+            .map_err(QueryError::Deserialize)?;
+
+        Ok(data)
+    }
+
+    pub(crate) async fn start_tx(&self, options: TransactionOptions) -> Result<TxId> {
+        self.executor()
+            .await?
+            .start_tx(self.query_schema.clone(), EngineProtocol::Graphql, options)
+            .await
+            .map_err(QueryError::from_engine)
+    }
+
+    pub(crate) async fn commit_tx(&self, tx_id: TxId) -> Result<()> {
+        self.executor()
+            .await?
+            .commit_tx(tx_id)
+            .await
+            .map_err(QueryError::from_engine)
+    }
+
+    pub(crate) async fn rollback_tx(&self, tx_id: TxId) -> Result<()> {
+        self.executor()
+            .await?
+            .rollback_tx(tx_id)
+            .await
+            .map_err(QueryError::from_engine)
+    }
+
+    async fn execute_all(
+        &self,
+        tx_id: Option<TxId>,
+        ops: Vec<Operation>,
+        transaction: Option<BatchDocumentTransaction>,
+    ) -> Result<Vec<Result<serde_value::Value>>> {
+        let response = self
+            .executor()
+            .await?
+            .execute_all(
+                tx_id,
+                ops,
+                transaction,
+                self.query_schema.clone(),
+                None,
+                EngineProtocol::Json,
+            )
+            .await
+            .map_err(QueryError::from_engine)?;
+
+        Ok(response
+            .into_iter()
+            .map(|result| {
+                let data: prisma_value::Item = result.map_err(QueryError::from_engine)?.data.into();
+
+                Ok(serde_value::to_value(data)
+                    .map_err(|e| e.to_string())
+                    .map_err(QueryError::Deserialize)?)
+            })
+            .collect())
+    }
+
+    /// Whether the database can cancel statements that run for too long itself.
+    fn has_statement_timeout(&self) -> bool {
+        matches!(self.provider, "postgresql" | "postgres" | "cockroachdb")
+    }
+
+    /// Runs `f` in a transaction with a `statement_timeout`, so that the database cancels statements
+    /// that take longer than `timeout` instead of finishing them after the client has stopped waiting.
+    /// `SET LOCAL` only lasts until the end of a transaction, so the query's interactive transaction
+    /// is used if it has one, and otherwise one is started for it.
+    async fn with_statement_timeout<T, Fut: Future<Output = Result<T>>>(
+        self: &Arc<Self>,
+        tx_id: Option<TxId>,
+        timeout: Duration,
+        isolation_level: Option<String>,
+        f: impl FnOnce(TxId) -> Fut,
+    ) -> Result<T> {
+        let millis = timeout.as_millis() as u64;
+
+        let (tx_id, transaction) = match tx_id {
+            Some(tx_id) => (tx_id, None),
+            None => {
+                // the transaction's own timeout must not end it before the statement's
+                let options = TransactionOptions::new(2000, millis + 5000, isolation_level);
+                let tx_id = self.start_tx(options).await?;
+
+                let transaction = StatementTimeoutTransaction {
+                    connector: self.clone(),
+                    tx_id: Some(tx_id.clone()),
+                };
+
+                (tx_id, Some(transaction))
+            }
+        };
+
+        self.execute_sql(&tx_id, format!("SET LOCAL statement_timeout = {millis}"))
+            .await?;
+
+        let result = f(tx_id.clone()).await;
+
+        match (transaction, result) {
+            (Some(transaction), Ok(value)) => transaction.commit().await.map(|_| value),
+            (Some(transaction), Err(e)) => {
+                transaction.rollback().await;
+                Err(e)
+            }
+            // otherwise the timeout would apply to the rest of the interactive transaction
+            (None, Ok(value)) => self
+                .execute_sql(&tx_id, "SET LOCAL statement_timeout TO DEFAULT".to_string())
+                .await
+                .map(|_| value),
+            (None, Err(e)) => Err(e),
+        }
+    }
+
+    async fn execute_sql(&self, tx_id: &TxId, sql: String) -> Result<()> {
+        let op = Operation::Write(Selection::new(
+            "executeRaw".to_string(),
+            None,
+            [
+                ("query".to_string(), PrismaValue::String(sql).into()),
+                (
+                    "parameters".to_string(),
+                    PrismaValue::String("[]".to_string()).into(),
+                ),
+            ],
+            [],
+        ));
+
+        self.execute(Some(tx_id.clone()), op).await.map(|_| ())
+    }
+
+    /// Drops the executor, closing its connection pool.
+    async fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+        self.executor.write().await.take();
+    }
+}
+
+/// A transaction started by `with_statement_timeout`, which is rolled back if it's dropped
+/// before being committed, eg. when the client stops waiting for the query.
+struct StatementTimeoutTransaction {
+    connector: Arc<ExecutorConnector>,
+    // taken when committing or rolling back
+    tx_id: Option<TxId>,
+}
+
+impl StatementTimeoutTransaction {
+    async fn commit(mut self) -> Result<()> {
+        match self.tx_id.take() {
+            Some(tx_id) => self.connector.commit_tx(tx_id).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn rollback(mut self) {
+        if let Some(tx_id) = self.tx_id.take() {
+            self.connector.rollback_tx(tx_id).await.ok();
+        }
+    }
+}
+
+impl Drop for StatementTimeoutTransaction {
+    fn drop(&mut self) {
+        if let (Some(tx_id), Ok(handle)) =
+            (self.tx_id.take(), tokio::runtime::Handle::try_current())
+        {
+            let connector = self.connector.clone();
+
+            // releases the connection, which the database has stopped the statement on
+            handle.spawn(async move {
+                connector.rollback_tx(tx_id).await.ok();
+            });
+        }
+    }
+}
+
+async fn load_executor(
+    datamodel: &psl::ValidatedSchema,
+    url: &str,
+) -> std::result::Result<Executor, CoreError> {
+    let config = &datamodel.configuration;
+    // the client can't be built without a datasource
+    let source = &config.datasources[0];
+
+    request_handlers::load_executor(source, config.preview_features(), url).await
+}
+
+/// Read replicas that reads outside of transactions are distributed across.
+pub(crate) struct Replicas {
+    connectors: Vec<Arc<ExecutorConnector>>,
+    next: AtomicUsize,
+}
+
+impl Replicas {
+    /// Picks a replica using round-robin.
+    fn next(&self) -> &Arc<ExecutorConnector> {
+        let i = self.next.fetch_add(1, Ordering::Relaxed);
+
+        &self.connectors[i % self.connectors.len()]
+    }
 }
 
 #[derive(Clone)]
 pub(crate) enum ExecutionEngine {
     Real {
         connector: Arc<ExecutorConnector>,
+        /// `None` if the client has no replicas.
+        replicas: Option<Arc<Replicas>>,
+        /// Whether reads are sent to the primary instead of a replica.
+        force_primary: bool,
         tx_id: Option<TxId>,
     },
     #[cfg(feature = "mocking")]
@@ -36,28 +368,38 @@ pub(crate) enum ExecutionEngine {
 }
 
 impl ExecutionEngine {
-    async fn execute(&self, op: Operation) -> Result<serde_value::Value> {
+    /// Executes `op`, having the database cancel it if it takes longer than `timeout`
+    /// when the database supports that.
+    pub(crate) async fn execute(
+        &self,
+        op: Operation,
+        timeout: Option<Duration>,
+    ) -> Result<serde_value::Value> {
         match self {
-            Self::Real { connector, tx_id } => {
-                let response = connector
-                    .executor
-                    .execute(
-                        tx_id.clone(),
-                        op,
-                        connector.query_schema.clone(),
-                        None,
-                        EngineProtocol::Json,
-                    )
-                    .await
-                    .map_err(|e| QueryError::Execute(e.into()))?;
-
-                let data: prisma_value::Item = response.data.into();
-
-                let data = serde_value::to_value(data)
-                    .map_err(|e| e.to_string())
-                    .map_err(QueryError::Deserialize)?;
+            Self::Real {
+                connector,
+                replicas,
+                force_primary,
+                tx_id,
+            } => {
+                // writes and everything in a transaction must go to the primary
+                let connector = match (&op, replicas, tx_id) {
+                    (Operation::Read(_), Some(replicas), None) if !*force_primary => {
+                        replicas.next()
+                    }
+                    _ => connector,
+                };
 
-                Ok(data)
+                match timeout.filter(|_| connector.has_statement_timeout()) {
+                    Some(timeout) => {
+                        connector
+                            .with_statement_timeout(tx_id.clone(), timeout, None, |tx_id| {
+                                connector.execute(Some(tx_id), op)
+                            })
+                            .await
+                    }
+                    None => connector.execute(tx_id.clone(), op).await,
+                }
             }
             #[cfg(feature = "mocking")]
             Self::Mock(store) => Ok(store.get_op(&op).await.expect("Mock data not found")),
@@ -67,36 +409,31 @@ impl ExecutionEngine {
     pub async fn execute_all(
         &self,
         ops: Vec<Operation>,
+        isolation_level: Option<String>,
+        timeout: Option<Duration>,
     ) -> Result<Vec<Result<serde_value::Value>>> {
         match self {
-            Self::Real { connector, .. } => {
-                let response = connector
-                    .executor
-                    .execute_all(
-                        None,
-                        ops,
-                        Some(BatchDocumentTransaction::new(None)),
-                        connector.query_schema.clone(),
-                        None,
-                        EngineProtocol::Json,
-                    )
-                    .await
-                    .map_err(|e| QueryError::Execute(e.into()))?;
+            // batches are transactional, so they always go to the primary
+            Self::Real {
+                connector, tx_id, ..
+            } => match timeout.filter(|_| connector.has_statement_timeout()) {
+                Some(timeout) => {
+                    connector
+                        .with_statement_timeout(tx_id.clone(), timeout, isolation_level, |tx_id| {
+                            connector.execute_all(Some(tx_id), ops, None)
+                        })
+                        .await
+                }
+                None => {
+                    // batches in an interactive transaction are already transactional
+                    let transaction = match tx_id {
+                        Some(_) => None,
+                        None => Some(BatchDocumentTransaction::new(isolation_level)),
+                    };
 
-                Ok(response
-                    .into_iter()
-                    .map(|result| {
-                        let data: prisma_value::Item = result
-                            .map_err(|e| QueryError::Execute(e.into()))?
-                            .data
-                            .into();
-
-                        Ok(serde_value::to_value(data)
-                            .map_err(|e| e.to_string())
-                            .map_err(QueryError::Deserialize)?)
-                    })
-                    .collect())
-            }
+                    connector.execute_all(tx_id.clone(), ops, transaction).await
+                }
+            },
             #[cfg(feature = "mocking")]
             Self::Mock(store) => {
                 let mut ret = vec![];
@@ -110,16 +447,65 @@ impl ExecutionEngine {
         }
     }
 
+    fn tx_id(&self) -> Option<&TxId> {
+        match self {
+            Self::Real { tx_id, .. } => tx_id.as_ref(),
+            #[cfg(feature = "mocking")]
+            Self::Mock(_) => None,
+        }
+    }
+
     fn with_tx_id(&self, tx_id: Option<TxId>) -> Self {
         match self {
-            Self::Real { connector, .. } => Self::Real {
+            Self::Real {
+                connector,
+                replicas,
+                force_primary,
+                ..
+            } => Self::Real {
                 connector: connector.clone(),
+                replicas: replicas.clone(),
+                force_primary: *force_primary,
                 tx_id,
             },
             #[cfg(feature = "mocking")]
             _ => self.clone(),
         }
     }
+
+    fn primary(&self) -> Self {
+        match self {
+            Self::Real {
+                connector,
+                replicas,
+                tx_id,
+                ..
+            } => Self::Real {
+                connector: connector.clone(),
+                replicas: replicas.clone(),
+                force_primary: true,
+                tx_id: tx_id.clone(),
+            },
+            #[cfg(feature = "mocking")]
+            _ => self.clone(),
+        }
+    }
+
+    /// The primary connector followed by the replicas.
+    fn connectors(&self) -> Vec<&ExecutorConnector> {
+        match self {
+            Self::Real {
+                connector,
+                replicas,
+                ..
+            } => std::iter::once(connector)
+                .chain(replicas.iter().flat_map(|replicas| &replicas.connectors))
+                .map(|connector| &**connector)
+                .collect(),
+            #[cfg(feature = "mocking")]
+            Self::Mock(_) => vec![],
+        }
+    }
 }
 
 /// The data held by the generated PrismaClient
@@ -128,94 +514,306 @@ impl ExecutionEngine {
 pub struct PrismaClientInternals {
     pub(crate) engine: ExecutionEngine,
     pub action_notifier: Arc<crate::ActionNotifier>,
+    pub(crate) middleware: MiddlewareStack,
+    pub(crate) pending_mutations: Option<PendingMutations>,
+    /// How many transactions deep the client is, with nested transactions using savepoints.
+    pub(crate) transaction_depth: u32,
+    pub(crate) transaction_hooks: Option<TransactionHooks>,
+    pub(crate) slow_query_threshold: Option<Duration>,
+    /// How long queries and batches can take before they're cancelled, unless they set their own timeout.
+    pub(crate) query_timeout: Option<Duration>,
+    pub(crate) metrics: Arc<MetricsRecorder>,
+    pub(crate) connection: Arc<ConnectionState>,
 }
 
 impl PrismaClientInternals {
-    pub(crate) async fn execute(&self, operation: Operation) -> Result<serde_value::Value> {
-        self.engine.execute(operation).await
-    }
-
-    // pub fn notify_model_mutation<'a, Action>(&self)
-    // where
-    //     Action: ModelQuery<'a>,
-    // {
-    //     match Action::TYPE {
-    //         ModelOperation::Write(action) => {
-    //             for callback in &self.action_notifier.model_mutation_callbacks {
-    //                 (callback)(ModelMutationCallbackData {
-    //                     model: Action::Types::MODEL,
-    //                     action,
-    //                 })
-    //             }
-    //         }
-    //         ModelOperation::Read(_) => {
-    //             println!("notify_model_mutation only acceps mutations, not queries!")
-    //         }
-    //     }
-    // }
+    pub(crate) async fn execute(
+        &self,
+        ctx: QueryContext,
+        timeout: Option<Duration>,
+    ) -> Result<serde_value::Value> {
+        let _query = self.connection.start_query()?;
+
+        let (model, action) = (ctx.model.clone(), ctx.action);
+        let mutation = ctx.mutation();
+        let metrics_key = query_key(&ctx);
+        let instrument = QueryInstrument::new(&ctx, self.engine.tx_id(), self.slow_query_threshold);
+
+        let timeout = timeout.or(self.query_timeout);
+        let result = with_timeout(
+            timeout,
+            Next {
+                engine: &self.engine,
+                middleware: &self.middleware,
+                timeout,
+            }
+            .run(ctx)
+            .instrument(instrument.span()),
+        )
+        .await
+        .map_err(|error| error.with_context(model, action));
 
+        let duration = instrument.finish(result.as_ref().ok().map(row_count));
+        self.metrics
+            .record_query(metrics_key, duration, result.as_ref().err());
+
+        let value = result?;
+
+        if let Some((model, action)) = mutation {
+            self.notify_model_mutations(vec![(model, action, value.clone())])
+                .await;
+        }
+
+        Ok(value)
+    }
+
+    pub(crate) async fn execute_all(
+        &self,
+        ctxs: Vec<QueryContext>,
+        isolation_level: Option<String>,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<Result<serde_value::Value>>> {
+        let _query = self.connection.start_query()?;
+
+        let mutations = ctxs.iter().map(QueryContext::mutation).collect::<Vec<_>>();
+        let metrics_keys = ctxs.iter().map(query_key).collect::<Vec<_>>();
+        let contexts = ctxs
+            .iter()
+            .map(|ctx| (ctx.model.clone(), ctx.action))
+            .collect::<Vec<_>>();
+        let instrument =
+            QueryInstrument::batch(&ctxs, self.engine.tx_id(), self.slow_query_threshold);
+
+        let timeout = timeout.or(self.query_timeout);
+        let result = with_timeout(
+            timeout,
+            NextBatch {
+                engine: &self.engine,
+                middleware: &self.middleware,
+                isolation_level,
+                timeout,
+            }
+            .run(ctxs)
+            .instrument(instrument.span()),
+        )
+        .await
+        .map(|values| {
+            values
+                .into_iter()
+                .zip(contexts)
+                .map(|(value, (model, action))| {
+                    value.map_err(|error| error.with_context(model, action))
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let duration = instrument.finish(result.as_ref().ok().map(|values| {
+            values
+                .iter()
+                .filter_map(|value| value.as_ref().ok())
+                .map(row_count)
+                .sum()
+        }));
+
+        for (i, key) in metrics_keys.into_iter().enumerate() {
+            let error = match &result {
+                Ok(values) => values.get(i).and_then(|value| value.as_ref().err()),
+                Err(error) => Some(error),
+            };
+
+            self.metrics.record_query(key, duration, error);
+        }
+
+        let values = result?;
+
+        // batches are transactional, so nothing was written if any query failed
+        if values.iter().all(|value| value.is_ok()) {
+            self.notify_model_mutations(
+                mutations
+                    .into_iter()
+                    .zip(&values)
+                    .filter_map(|(mutation, value)| {
+                        let (model, action) = mutation?;
+                        Some((model, action, value.as_ref().ok()?.clone()))
+                    })
+                    .collect(),
+            )
+            .await;
+        }
+
+        Ok(values)
+    }
+
+    /// Runs the model mutation callbacks for writes made outside of a transaction,
+    /// or saves them until the transaction commits.
+    async fn notify_model_mutations(
+        &self,
+        mutations: Vec<(String, ModelWriteOperation, serde_value::Value)>,
+    ) {
+        if self.action_notifier.model_mutation_callbacks.is_empty() || mutations.is_empty() {
+            return;
+        }
+
+        let mutations = mutations
+            .into_iter()
+            .map(|(model, action, value)| ModelMutationCallbackData {
+                model,
+                action,
+                result: ModelMutationResult::new(action, value),
+            })
+            .collect();
+
+        match &self.pending_mutations {
+            Some(pending) => {
+                pending.lock().unwrap().extend(mutations);
+            }
+            None => self.action_notifier.notify(mutations).await,
+        }
+    }
+
+    /// Runs the model mutation callbacks for writes made in a transaction that has committed.
+    pub(crate) async fn notify_committed_mutations(&self, pending: Option<PendingMutations>) {
+        if let Some(pending) = pending {
+            let mutations = std::mem::take(&mut *pending.lock().unwrap());
+
+            self.action_notifier.notify(mutations).await;
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
         url: Option<String>,
+        replica_urls: Vec<String>,
         action_notifier: ActionNotifier,
-        datamodel: &str,
+        middleware: Vec<Box<dyn Middleware>>,
+        slow_query_threshold: Option<Duration>,
+        query_timeout: Option<Duration>,
+        connection_options: ConnectionOptions,
+        lazy_connect: bool,
+        embedded_schema: EmbeddedSchema<'_>,
     ) -> std::result::Result<Self, NewClientError> {
-        let schema = Arc::new(psl::validate(datamodel.into()));
-        let config = &schema.configuration;
+        let datamodel = Arc::new(psl::validate(embedded_schema.datamodel.into()));
+        let config = &datamodel.configuration;
 
         let source = config
             .datasources
             .first()
-            .expect("Please supply a datasource in your schema.prisma file");
+            .ok_or(NewClientError::MissingDatasource)?;
 
         let url = match url {
             Some(url) => url,
             None => {
                 let url = match source.load_url(|key| dotenvy::var(key).ok()) {
-                    Ok(url) => Some(url),
-                    Err(_) => source.load_shadow_database_url()?,
+                    Ok(url) => url,
+                    Err(diagnostics) => match source.load_shadow_database_url()? {
+                        Some(url) => url,
+                        None => {
+                            return Err(match &source.url.from_env_var {
+                                Some(var) if dotenvy::var(var).is_err() => {
+                                    NewClientError::MissingEnvVar(var.clone())
+                                }
+                                _ => NewClientError::InvalidUrl(diagnostics.to_string()),
+                            })
+                        }
+                    },
+                };
+
+                match source.active_provider {
+                    "sqlite" => resolve_sqlite_url(url, embedded_schema.path),
+                    _ => url,
                 }
-                .expect("Datasource could not be fetched, please check your schema.prisma file or your environment variables");
-
-                match url.starts_with("file:") {
-                    true => {
-                        let path = url.split(':').nth(1).unwrap();
-                        if std::path::Path::new("./prisma/schema.prisma").exists() {
-                            format!("file:./prisma/{path}")
-                        } else {
-                            url
+            }
+        };
+
+        let query_schema = Arc::new(schema::build(datamodel.clone(), true));
+
+        let connection_options = &connection_options;
+        let load_connector = |url: String| {
+            let datamodel = datamodel.clone();
+            let query_schema = query_schema.clone();
+
+            async move {
+                let (url, options) = connection_options.merge(&url, source.active_provider)?;
+
+                let mut connector = ExecutorConnector {
+                    executor: RwLock::new(None),
+                    closed: AtomicBool::new(false),
+                    datamodel,
+                    query_schema,
+                    url,
+                    provider: source.active_provider,
+                    options,
+                };
+
+                match load_executor(&connector.datamodel, &connector.url).await {
+                    Ok(executor) => {
+                        if !lazy_connect {
+                            executor.primary_connector().get_connection().await?;
+                            connector.set_journal_mode(&executor).await?;
                         }
+
+                        *connector.executor.get_mut() = Some(executor);
                     }
-                    _ => url,
+                    // lazy clients try loading it again when they're first used
+                    Err(_) if lazy_connect => {}
+                    Err(e) => return Err(e.into()),
                 }
+
+                Ok::<_, NewClientError>(connector)
             }
         };
 
-        let executor =
-            request_handlers::load_executor(source, config.preview_features(), &url).await?;
+        let connector = load_connector(url).await?;
 
-        executor.primary_connector().get_connection().await?;
+        let mut replicas = vec![];
+        for url in replica_urls {
+            replicas.push(Arc::new(load_connector(url).await?));
+        }
 
         Ok(Self {
             engine: ExecutionEngine::Real {
-                connector: Arc::new(ExecutorConnector {
-                    executor,
-                    query_schema: Arc::new(schema::build(schema.clone(), true)),
-                    url,
+                connector: Arc::new(connector),
+                replicas: (!replicas.is_empty()).then(|| {
+                    Arc::new(Replicas {
+                        connectors: replicas,
+                        next: AtomicUsize::new(0),
+                    })
                 }),
+                force_primary: false,
                 tx_id: None,
             },
             action_notifier: Arc::new(action_notifier),
+            middleware: Arc::new(middleware),
+            pending_mutations: None,
+            transaction_depth: 0,
+            transaction_hooks: None,
+            slow_query_threshold,
+            query_timeout,
+            metrics: Default::default(),
+            connection: Default::default(),
         })
     }
 
     #[cfg(feature = "mocking")]
-    pub fn new_mock(action_notifier: ActionNotifier) -> (Self, crate::MockStore) {
+    pub fn new_mock(
+        action_notifier: ActionNotifier,
+        middleware: Vec<Box<dyn Middleware>>,
+        slow_query_threshold: Option<Duration>,
+    ) -> (Self, crate::MockStore) {
         let mock_store = crate::MockStore::new();
 
         (
             Self {
                 engine: ExecutionEngine::Mock(mock_store.clone()),
                 action_notifier: Arc::new(action_notifier),
+                middleware: Arc::new(middleware),
+                pending_mutations: None,
+                transaction_depth: 0,
+                transaction_hooks: None,
+                slow_query_threshold,
+                query_timeout: None,
+                metrics: Default::default(),
+                connection: Default::default(),
             },
             mock_store,
         )
@@ -231,10 +829,188 @@ impl PrismaClientInternals {
 
     pub fn with_tx_id(&self, tx_id: Option<TxId>) -> Self {
         Self {
+            pending_mutations: tx_id.as_ref().map(|_| Default::default()),
+            transaction_depth: tx_id.as_ref().map_or(0, |_| 1),
+            transaction_hooks: tx_id.as_ref().map(|_| Default::default()),
             engine: self.engine.with_tx_id(tx_id),
             action_notifier: self.action_notifier.clone(),
+            middleware: self.middleware.clone(),
+            slow_query_threshold: self.slow_query_threshold,
+            query_timeout: self.query_timeout,
+            metrics: self.metrics.clone(),
+            connection: self.connection.clone(),
+        }
+    }
+
+    /// Establishes a connection to the primary database and each replica if their pools don't have one,
+    /// returning how long it took.
+    /// If the client was built with `lazy_connect` and couldn't load the database's executor,
+    /// loading it is retried first.
+    pub async fn connect(&self) -> Result<Duration> {
+        self.connection.check_connected()?;
+
+        let start = Instant::now();
+
+        for connector in self.engine.connectors() {
+            connector.connect().await?;
+        }
+
+        Ok(start.elapsed())
+    }
+
+    /// Checks that the primary database and each replica are reachable by running a trivial query,
+    /// returning the round trip latency of all of them.
+    pub async fn health_check(&self) -> Result<Duration> {
+        let _query = self.connection.start_query()?;
+
+        let start = Instant::now();
+
+        for connector in self.engine.connectors() {
+            let (op, _) = match connector.provider {
+                "mongodb" => {
+                    RunCommandRaw::<serde_json::Value>::new(self, serde_json::json!({ "ping": 1 }))
+                        .graphql()
+                }
+                provider => {
+                    QueryRaw::<serde_json::Value>::new(self, Raw::new("SELECT 1", vec![]), provider)
+                        .graphql()
+                }
+            };
+
+            // executed on the connector directly so that middleware and metrics don't see it
+            connector.execute(None, op).await?;
+        }
+
+        Ok(start.elapsed())
+    }
+
+    /// Rejects new queries and transactions, waits for in-flight queries to finish,
+    /// rolls back interactive transactions that are still open,
+    /// then closes the connection pools of the primary database and replicas.
+    pub async fn disconnect(&self) {
+        let open_transactions = self.connection.disconnect().await;
+
+        match &self.engine {
+            ExecutionEngine::Real { connector, .. } => {
+                for tx_id in open_transactions {
+                    // the transaction's own commit or rollback fails once the pool is closed
+                    connector.rollback_tx(tx_id).await.ok();
+                }
+            }
+            #[cfg(feature = "mocking")]
+            ExecutionEngine::Mock(_) => {}
+        }
+
+        for connector in self.engine.connectors() {
+            connector.close().await;
+        }
+    }
+
+    /// The provider, redacted URL and options the client connected with.
+    pub fn connection_config(&self) -> ConnectionConfig {
+        match &self.engine {
+            #[cfg(feature = "mocking")]
+            ExecutionEngine::Mock(_) => ConnectionConfig {
+                provider: "mock",
+                url: "mock".to_string(),
+                replica_urls: vec![],
+                options: Default::default(),
+            },
+            ExecutionEngine::Real {
+                connector,
+                replicas,
+                ..
+            } => ConnectionConfig {
+                provider: connector.provider,
+                url: redact_url(&connector.url, connector.provider),
+                replica_urls: replicas
+                    .iter()
+                    .flat_map(|replicas| &replicas.connectors)
+                    .map(|replica| redact_url(&replica.url, replica.provider))
+                    .collect(),
+                options: connector.options.clone(),
+            },
+        }
+    }
+
+    /// A copy of the client that executes every query on the primary database instead of a replica.
+    pub fn primary(&self) -> Self {
+        Self {
+            engine: self.engine.primary(),
+            ..self.clone()
         }
     }
+
+    /// Registers a hook to run once the client's transaction commits.
+    /// Outside of a transaction the hook runs immediately.
+    pub fn on_commit(&self, hook: impl FnOnce() + Send + 'static) {
+        match &self.transaction_hooks {
+            Some(hooks) => hooks.on_commit(Box::new(hook)),
+            None => hook(),
+        }
+    }
+
+    /// Registers a hook to run if the client's transaction rolls back.
+    /// Outside of a transaction the hook is never run.
+    pub fn on_rollback(&self, hook: impl FnOnce() + Send + 'static) {
+        if let Some(hooks) = &self.transaction_hooks {
+            hooks.on_rollback(Box::new(hook));
+        }
+    }
+
+    /// A snapshot of the metrics collected by this client and its transactions.
+    pub fn metrics(&self) -> Metrics {
+        self.metrics.snapshot()
+    }
+}
+
+/// Cancels `future` if it doesn't finish within `timeout`.
+/// Databases with a statement timeout stop executing the statement themselves,
+/// see `ExecutorConnector::with_statement_timeout`.
+async fn with_timeout<T>(
+    timeout: Option<Duration>,
+    future: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, future)
+            .await
+            .unwrap_or(Err(QueryError::Timeout(timeout))),
+        None => future.await,
+    }
+}
+
+/// The schema a client was generated from, embedded by the generator.
+pub struct EmbeddedSchema<'a> {
+    pub datamodel: &'a str,
+    /// The path of the schema file when the client was generated.
+    pub path: &'a str,
+}
+
+/// Resolves a relative SQLite path against the directory containing the schema file,
+/// rather than the process' working directory, as Prisma does.
+fn resolve_sqlite_url(url: String, schema_path: &str) -> String {
+    let path = match url.strip_prefix("file:") {
+        Some(path) => path,
+        None => return url,
+    };
+
+    let (path, params) = match path.split_once('?') {
+        Some((path, params)) => (path, Some(params)),
+        None => (path, None),
+    };
+
+    let schema_dir = Path::new(schema_path).parent();
+
+    match schema_dir {
+        Some(dir) if Path::new(path).is_relative() => format!(
+            "file:{}{}",
+            dir.join(path).display(),
+            params
+                .map(|params| format!("?{params}"))
+                .unwrap_or_default()
+        ),
+        _ => url,
+    }
 }
 
 trait DiagnosticsToString {
@@ -258,6 +1034,18 @@ pub enum NewClientError {
 
     #[error("Error getting database connection: {0}")]
     Connection(#[from] query_core::ConnectorError),
+
+    #[error("Invalid connection option: {0}")]
+    InvalidConnectionOption(String),
+
+    #[error("No datasource was found in the schema")]
+    MissingDatasource,
+
+    #[error("Environment variable '{0}' containing the datasource URL is not set")]
+    MissingEnvVar(String),
+
+    #[error("Invalid datasource URL: {0}")]
+    InvalidUrl(String),
 }
 
 impl From<Diagnostics> for NewClientError {
@@ -0,0 +1,428 @@
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use query_core::TxId;
+use tokio::sync::Notify;
+
+use crate::{NewClientError, QueryError};
+
+/// Connection pool and engine options that are merged into the datasource URL,
+/// replacing any of the same options already present in it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectionOptions {
+    /// The maximum number of connections in the pool.
+    pub connection_limit: Option<u32>,
+    /// How long to wait for a connection from the pool before failing.
+    pub pool_timeout: Option<Duration>,
+    /// How long to wait for a new connection to be established.
+    pub connect_timeout: Option<Duration>,
+    /// How long to wait for a query's response.
+    /// For SQLite this is the `busy_timeout` pragma, the time to wait for a locked database.
+    pub socket_timeout: Option<Duration>,
+    /// How many prepared statements are cached per connection.
+    pub statement_cache_size: Option<u32>,
+    /// The SQLite `journal_mode` pragma, set when the client connects.
+    pub sqlite_journal_mode: Option<SqliteJournalMode>,
+}
+
+/// A value of SQLite's `journal_mode` pragma.
+/// Unlike most pragmas it is stored in the database file,
+/// so it applies to every connection in the pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqliteJournalMode {
+    Delete,
+    Truncate,
+    Persist,
+    Memory,
+    Wal,
+    Off,
+}
+
+impl SqliteJournalMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Delete => "DELETE",
+            Self::Truncate => "TRUNCATE",
+            Self::Persist => "PERSIST",
+            Self::Memory => "MEMORY",
+            Self::Wal => "WAL",
+            Self::Off => "OFF",
+        }
+    }
+}
+
+/// The configuration a client connected with, returned by `PrismaClient::_connection_config`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionConfig {
+    pub provider: &'static str,
+    /// The datasource URL with any password redacted.
+    pub url: String,
+    /// The URLs of the read replicas with any passwords redacted.
+    pub replica_urls: Vec<String>,
+    /// The options in the datasource URL, including those that were already in it.
+    pub options: ConnectionOptions,
+}
+
+#[derive(Clone, Copy)]
+enum Unit {
+    Count,
+    Seconds,
+    Millis,
+}
+
+#[derive(Clone, Copy)]
+enum ConnectionOption {
+    ConnectionLimit,
+    PoolTimeout,
+    ConnectTimeout,
+    SocketTimeout,
+    StatementCacheSize,
+}
+
+impl ConnectionOption {
+    const ALL: [Self; 5] = [
+        Self::ConnectionLimit,
+        Self::PoolTimeout,
+        Self::ConnectTimeout,
+        Self::SocketTimeout,
+        Self::StatementCacheSize,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::ConnectionLimit => "connection_limit",
+            Self::PoolTimeout => "pool_timeout",
+            Self::ConnectTimeout => "connect_timeout",
+            Self::SocketTimeout => "socket_timeout",
+            Self::StatementCacheSize => "statement_cache_size",
+        }
+    }
+
+    /// The URL parameter for the option, if the provider supports it.
+    fn param(self, provider: &str) -> Option<(&'static str, Unit)> {
+        Some(match (provider, self) {
+            ("postgresql" | "cockroachdb" | "mysql", _) => (
+                self.name(),
+                match self {
+                    Self::ConnectionLimit | Self::StatementCacheSize => Unit::Count,
+                    _ => Unit::Seconds,
+                },
+            ),
+            ("sqlite", Self::ConnectionLimit) => ("connection_limit", Unit::Count),
+            ("sqlite", Self::SocketTimeout) => ("socket_timeout", Unit::Seconds),
+            ("sqlserver", Self::ConnectionLimit) => ("connectionLimit", Unit::Count),
+            ("sqlserver", Self::PoolTimeout) => ("poolTimeout", Unit::Seconds),
+            ("sqlserver", Self::ConnectTimeout) => ("connectTimeout", Unit::Seconds),
+            ("sqlserver", Self::SocketTimeout) => ("socketTimeout", Unit::Seconds),
+            ("mongodb", Self::ConnectionLimit) => ("maxPoolSize", Unit::Count),
+            ("mongodb", Self::ConnectTimeout) => ("connectTimeoutMS", Unit::Millis),
+            ("mongodb", Self::SocketTimeout) => ("socketTimeoutMS", Unit::Millis),
+            _ => return None,
+        })
+    }
+}
+
+enum Value {
+    Count(u32),
+    Duration(Duration),
+}
+
+impl ConnectionOptions {
+    fn get(&self, option: ConnectionOption) -> Option<Value> {
+        match option {
+            ConnectionOption::ConnectionLimit => self.connection_limit.map(Value::Count),
+            ConnectionOption::PoolTimeout => self.pool_timeout.map(Value::Duration),
+            ConnectionOption::ConnectTimeout => self.connect_timeout.map(Value::Duration),
+            ConnectionOption::SocketTimeout => self.socket_timeout.map(Value::Duration),
+            ConnectionOption::StatementCacheSize => self.statement_cache_size.map(Value::Count),
+        }
+    }
+
+    fn set(&mut self, option: ConnectionOption, value: Value) {
+        match (option, value) {
+            (ConnectionOption::ConnectionLimit, Value::Count(v)) => self.connection_limit = Some(v),
+            (ConnectionOption::PoolTimeout, Value::Duration(v)) => self.pool_timeout = Some(v),
+            (ConnectionOption::ConnectTimeout, Value::Duration(v)) => {
+                self.connect_timeout = Some(v)
+            }
+            (ConnectionOption::SocketTimeout, Value::Duration(v)) => self.socket_timeout = Some(v),
+            (ConnectionOption::StatementCacheSize, Value::Count(v)) => {
+                self.statement_cache_size = Some(v)
+            }
+            _ => {}
+        }
+    }
+
+    /// Validates the options and merges them into `url`,
+    /// returning the new URL and the options it ends up containing.
+    pub(crate) fn merge(
+        &self,
+        url: &str,
+        provider: &str,
+    ) -> Result<(String, ConnectionOptions), NewClientError> {
+        let mut url = ParsedUrl::new(url, provider);
+
+        // pragmas can't be set in the URL, so the journal mode is set once connected
+        if self.sqlite_journal_mode.is_some() && provider != "sqlite" {
+            return Err(NewClientError::InvalidConnectionOption(format!(
+                "sqlite_journal_mode is not supported by {provider}"
+            )));
+        }
+
+        for option in ConnectionOption::ALL {
+            let value = match self.get(option) {
+                Some(value) => value,
+                None => continue,
+            };
+
+            let (param, unit) = option.param(provider).ok_or_else(|| {
+                NewClientError::InvalidConnectionOption(format!(
+                    "{} is not supported by {provider}",
+                    option.name()
+                ))
+            })?;
+
+            let value = match (value, unit) {
+                (Value::Count(0), _) if matches!(option, ConnectionOption::ConnectionLimit) => {
+                    return Err(NewClientError::InvalidConnectionOption(
+                        "connection_limit must be greater than 0".to_string(),
+                    ))
+                }
+                (Value::Count(count), _) => count.to_string(),
+                (Value::Duration(duration), Unit::Millis) => duration.as_millis().to_string(),
+                (Value::Duration(duration), _) => {
+                    if duration.subsec_nanos() != 0 {
+                        return Err(NewClientError::InvalidConnectionOption(format!(
+                            "{} must be a whole number of seconds for {provider}",
+                            option.name()
+                        )));
+                    }
+
+                    duration.as_secs().to_string()
+                }
+            };
+
+            url.set(param, value);
+        }
+
+        let mut effective = ConnectionOptions {
+            sqlite_journal_mode: self.sqlite_journal_mode,
+            ..Default::default()
+        };
+
+        for option in ConnectionOption::ALL {
+            let (param, unit) = match option.param(provider) {
+                Some(param) => param,
+                None => continue,
+            };
+
+            let value = url.get(param).and_then(|value| match unit {
+                Unit::Count => value.parse().ok().map(Value::Count),
+                Unit::Seconds => value
+                    .parse()
+                    .ok()
+                    .map(|secs| Value::Duration(Duration::from_secs(secs))),
+                Unit::Millis => value
+                    .parse()
+                    .ok()
+                    .map(|millis| Value::Duration(Duration::from_millis(millis))),
+            });
+
+            if let Some(value) = value {
+                effective.set(option, value);
+            }
+        }
+
+        Ok((url.to_string(), effective))
+    }
+}
+
+/// A datasource URL split into its parameters.
+/// SQL Server URLs use `;key=value` parameters, the rest use a `?key=value&..` query string.
+struct ParsedUrl {
+    base: String,
+    params: Vec<(String, String)>,
+    sqlserver: bool,
+}
+
+impl ParsedUrl {
+    fn new(url: &str, provider: &str) -> Self {
+        let sqlserver = provider == "sqlserver";
+
+        let (base, params) = match sqlserver {
+            true => url.split_once(';').unwrap_or((url, "")),
+            false => url.split_once('?').unwrap_or((url, "")),
+        };
+
+        let params = params
+            .split(if sqlserver { ';' } else { '&' })
+            .filter(|param| !param.is_empty())
+            .map(|param| {
+                let (key, value) = param.split_once('=').unwrap_or((param, ""));
+                (key.to_string(), value.to_string())
+            })
+            .collect();
+
+        Self {
+            base: base.to_string(),
+            params,
+            sqlserver,
+        }
+    }
+
+    fn matches(&self, key: &str, param: &str) -> bool {
+        match self.sqlserver {
+            // sql server parameters are case insensitive
+            true => key.eq_ignore_ascii_case(param),
+            false => key == param,
+        }
+    }
+
+    fn get(&self, param: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| self.matches(key, param))
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn set(&mut self, param: &str, value: String) {
+        let sqlserver = self.sqlserver;
+
+        self.params.retain(|(key, _)| match sqlserver {
+            true => !key.eq_ignore_ascii_case(param),
+            false => key != param,
+        });
+        self.params.push((param.to_string(), value));
+    }
+}
+
+impl fmt::Display for ParsedUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.base)?;
+
+        let separator = if self.sqlserver { ';' } else { '&' };
+
+        for (i, (key, value)) in self.params.iter().enumerate() {
+            let prefix = match (i, self.sqlserver) {
+                (0, false) => '?',
+                (0, true) => ';',
+                _ => separator,
+            };
+
+            write!(f, "{prefix}{key}={value}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Replaces the password in a datasource URL so that it can be logged.
+pub(crate) fn redact_url(url: &str, provider: &str) -> String {
+    let mut parsed = ParsedUrl::new(url, provider);
+
+    if parsed.sqlserver {
+        for (key, value) in &mut parsed.params {
+            if key.eq_ignore_ascii_case("password") {
+                *value = "****".to_string();
+            }
+        }
+    }
+
+    if let Some(scheme_end) = parsed.base.find("://") {
+        let authority_start = scheme_end + 3;
+        let authority_end = parsed.base[authority_start..]
+            .find('/')
+            .map_or(parsed.base.len(), |i| authority_start + i);
+
+        if let Some(at) = parsed.base[authority_start..authority_end].rfind('@') {
+            let userinfo = &parsed.base[authority_start..authority_start + at];
+
+            if let Some(colon) = userinfo.find(':') {
+                parsed
+                    .base
+                    .replace_range(authority_start + colon + 1..authority_start + at, "****");
+            }
+        }
+    }
+
+    parsed.to_string()
+}
+
+/// Tracks in-flight queries and open interactive transactions
+/// so that `_disconnect` can wait for the queries and roll back the transactions.
+#[derive(Default)]
+pub(crate) struct ConnectionState {
+    disconnected: AtomicBool,
+    in_flight: AtomicUsize,
+    drained: Notify,
+    transactions: Mutex<Vec<TxId>>,
+}
+
+impl ConnectionState {
+    /// Registers a query until the returned guard is dropped,
+    /// failing if the client has been disconnected.
+    pub fn start_query(&self) -> Result<InFlightQuery<'_>, QueryError> {
+        // incremented before checking so that `disconnect` can't miss the query
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        let query = InFlightQuery(self);
+
+        self.check_connected().map(|_| query)
+    }
+
+    pub fn check_connected(&self) -> Result<(), QueryError> {
+        match self.disconnected.load(Ordering::SeqCst) {
+            true => Err(QueryError::Disconnected),
+            false => Ok(()),
+        }
+    }
+
+    /// Registers a transaction that has been started until `finish_transaction` is called,
+    /// failing if the client was disconnected while it was starting.
+    pub fn start_transaction(&self, tx_id: TxId) -> Result<(), QueryError> {
+        let mut transactions = self.transactions.lock().unwrap();
+
+        // checked while locked so that `disconnect` can't miss the transaction
+        self.check_connected()?;
+        transactions.push(tx_id);
+
+        Ok(())
+    }
+
+    pub fn finish_transaction(&self, tx_id: &TxId) {
+        self.transactions.lock().unwrap().retain(|id| id != tx_id);
+    }
+
+    /// Rejects new queries and transactions and waits for in-flight queries to finish,
+    /// returning the transactions that are still open so that they can be rolled back.
+    pub async fn disconnect(&self) -> Vec<TxId> {
+        self.disconnected.store(true, Ordering::SeqCst);
+
+        loop {
+            let drained = self.drained.notified();
+
+            if self.in_flight.load(Ordering::SeqCst) == 0 {
+                break;
+            }
+
+            drained.await;
+        }
+
+        std::mem::take(&mut *self.transactions.lock().unwrap())
+    }
+}
+
+pub(crate) struct InFlightQuery<'a>(&'a ConnectionState);
+
+impl<'a> Drop for InFlightQuery<'a> {
+    fn drop(&mut self) {
+        if self.0.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.0.drained.notify_waiters();
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use serde::Serialize;
+
+use crate::{ErrorKind, QueryError};
+
+/// The JSON body of an HTTP response created from a `QueryError`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ErrorResponseBody {
+    /// A snake_case version of the error's `ErrorKind`, eg. `unique_constraint_violation`.
+    pub kind: &'static str,
+    /// The Prisma error code, if there is one.
+    pub code: Option<String>,
+    /// A fixed description of the error's kind, followed by its Prisma error code if it has one.
+    /// Server errors use a generic message to avoid leaking details about the database.
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// The fields of a violated unique constraint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<Vec<String>>,
+    /// The field of a violated foreign key constraint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+}
+
+impl QueryError {
+    /// The HTTP status code that best describes the error.
+    pub fn http_status(&self) -> u16 {
+        match self.kind() {
+            ErrorKind::RecordNotFound => 404,
+            ErrorKind::UniqueConstraintViolation { .. } | ErrorKind::TransactionConflict => 409,
+            ErrorKind::ForeignKeyViolation { .. } => 422,
+            ErrorKind::NullConstraintViolation { .. } | ErrorKind::Validation => 400,
+            ErrorKind::ConnectionFailed => 503,
+            ErrorKind::Timeout => 504,
+            ErrorKind::Other { .. } | ErrorKind::Unknown => 500,
+        }
+    }
+
+    pub fn response_body(&self) -> ErrorResponseBody {
+        let kind = self.kind();
+
+        let code = match self {
+            Self::Execute(error) | Self::RecordNotFound(error) => {
+                error.as_known().map(|error| error.error_code.to_string())
+            }
+            _ => None,
+        };
+
+        // the error's own message can contain database details and user input,
+        // so a fixed message for its kind is used instead
+        let message = match (client_error_message(&kind), &code) {
+            (Some(message), Some(code)) => format!("{message} ({code})"),
+            (Some(message), None) => message.to_string(),
+            (None, _) => {
+                "Internal server error occurred while completing database operation!".to_string()
+            }
+        };
+
+        let (target, field) = match &kind {
+            ErrorKind::UniqueConstraintViolation { target } => (Some(target.clone()), None),
+            ErrorKind::ForeignKeyViolation { field } => (None, field.clone()),
+            _ => (None, None),
+        };
+
+        ErrorResponseBody {
+            kind: kind.name(),
+            code,
+            message,
+            model: self.model().map(ToString::to_string),
+            target,
+            field,
+        }
+    }
+}
+
+/// The message for kinds of errors that result in a 4xx status.
+fn client_error_message(kind: &ErrorKind) -> Option<&'static str> {
+    Some(match kind {
+        ErrorKind::RecordNotFound => "Record not found",
+        ErrorKind::UniqueConstraintViolation { .. } => "Unique constraint violated",
+        ErrorKind::ForeignKeyViolation { .. } => "Foreign key constraint violated",
+        ErrorKind::NullConstraintViolation { .. } => "Required value is null",
+        ErrorKind::Validation => "Invalid query argument",
+        ErrorKind::TransactionConflict => "Transaction conflicted with another and can be retried",
+        _ => return None,
+    })
+}
+
+#[cfg(feature = "axum")]
+impl axum::response::IntoResponse for QueryError {
+    fn into_response(self) -> axum::response::Response {
+        let status = axum::http::StatusCode::from_u16(self.http_status())
+            .unwrap_or(axum::http::StatusCode::INTERNAL_SERVER_ERROR);
+
+        (status, axum::Json(self.response_body())).into_response()
+    }
+}
+
+#[cfg(feature = "actix-web")]
+impl actix_web::ResponseError for QueryError {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        actix_web::http::StatusCode::from_u16(self.http_status())
+            .unwrap_or(actix_web::http::StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    fn error_response(&self) -> actix_web::HttpResponse {
+        actix_web::HttpResponse::build(actix_web::ResponseError::status_code(self))
+            .json(self.response_body())
+    }
+}
+
+#[cfg(feature = "rocket")]
+impl<'r> rocket::response::Responder<'r, 'static> for QueryError {
+    fn respond_to(self, _: &'r rocket::Request<'_>) -> rocket::response::Result<'static> {
+        let body = serde_json::to_string(&self.response_body())
+            .map_err(|_| rocket::http::Status::InternalServerError)?;
+
+        rocket::Response::build()
+            .status(rocket::http::Status::new(self.http_status()))
+            .header(rocket::http::ContentType::JSON)
+            .sized_body(body.len(), std::io::Cursor::new(body))
+            .ok()
+    }
+}
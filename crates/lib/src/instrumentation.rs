@@ -0,0 +1,141 @@
+use std::time::{Duration, Instant};
+
+use query_core::{ArgumentValue, TxId};
+use tracing::{field, Span};
+
+use crate::QueryContext;
+
+/// Tracks the span, duration and slow query logging of an operation or batch.
+pub(crate) struct QueryInstrument {
+    span: Span,
+    start: Instant,
+    slow_query: Option<(Duration, QueryArguments)>,
+}
+
+/// The arguments of an operation or batch, kept so they can be logged if the query is slow.
+enum QueryArguments {
+    Operation(Vec<(String, ArgumentValue)>),
+    Batch(Vec<(String, Vec<(String, ArgumentValue)>)>),
+}
+
+impl QueryArguments {
+    fn format(&self) -> String {
+        match self {
+            Self::Operation(arguments) => format!("{arguments:?}"),
+            Self::Batch(operations) => operations
+                .iter()
+                .map(|(name, arguments)| format!("{name}: {arguments:?}"))
+                .collect::<Vec<_>>()
+                .join(", "),
+        }
+    }
+}
+
+impl QueryInstrument {
+    pub fn new(
+        ctx: &QueryContext,
+        tx_id: Option<&TxId>,
+        slow_query_threshold: Option<Duration>,
+    ) -> Self {
+        let span = tracing::info_span!(
+            "prisma_query",
+            model = ctx.model.as_deref(),
+            action = ctx.action.map(|action| action.name()),
+            tx_id = tx_id.map(field::display),
+            duration_ms = field::Empty,
+            rows = field::Empty,
+        );
+
+        Self::start(span, slow_query_threshold, || {
+            QueryArguments::Operation(ctx.operation.arguments().to_vec())
+        })
+    }
+
+    pub fn batch(
+        ctxs: &[QueryContext],
+        tx_id: Option<&TxId>,
+        slow_query_threshold: Option<Duration>,
+    ) -> Self {
+        let span = tracing::info_span!(
+            "prisma_batch",
+            operations = ctxs.len(),
+            tx_id = tx_id.map(field::display),
+            duration_ms = field::Empty,
+            rows = field::Empty,
+        );
+
+        Self::start(span, slow_query_threshold, || {
+            QueryArguments::Batch(
+                ctxs.iter()
+                    .map(|ctx| {
+                        (
+                            ctx.operation.name().to_string(),
+                            ctx.operation.arguments().to_vec(),
+                        )
+                    })
+                    .collect(),
+            )
+        })
+    }
+
+    // arguments are only kept when they may need to be logged,
+    // and are only formatted once the query turns out to be slow
+    fn start(
+        span: Span,
+        slow_query_threshold: Option<Duration>,
+        arguments: impl FnOnce() -> QueryArguments,
+    ) -> Self {
+        Self {
+            span,
+            start: Instant::now(),
+            slow_query: slow_query_threshold.map(|threshold| (threshold, arguments())),
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        self.span.clone()
+    }
+
+    /// Records the duration and number of rows returned, logging the query if it was slow.
+    pub fn finish(self, rows: Option<u64>) -> Duration {
+        let elapsed = self.start.elapsed();
+
+        self.span.record("duration_ms", elapsed.as_millis() as u64);
+        if let Some(rows) = rows {
+            self.span.record("rows", rows);
+        }
+
+        if let Some((threshold, arguments)) = self.slow_query {
+            if elapsed >= threshold {
+                let arguments = arguments.format();
+
+                tracing::warn!(
+                    parent: &self.span,
+                    duration_ms = elapsed.as_millis() as u64,
+                    threshold_ms = threshold.as_millis() as u64,
+                    %arguments,
+                    "slow query"
+                );
+            }
+        }
+
+        elapsed
+    }
+}
+
+/// The number of rows in a value returned by the query engine.
+pub(crate) fn row_count(value: &serde_value::Value) -> u64 {
+    use serde_value::Value;
+
+    match value {
+        Value::Seq(items) => items.len() as u64,
+        // the result of a create_many, update_many or delete_many
+        Value::Map(map) if map.len() == 1 => match map.get(&Value::String("count".to_string())) {
+            Some(Value::I64(count)) => *count as u64,
+            Some(Value::U64(count)) => *count,
+            _ => 1,
+        },
+        Value::Unit | Value::Option(None) => 0,
+        _ => 1,
+    }
+}
@@ -9,7 +9,12 @@ compile_error!("No connector feature enabled. Please enable a connector feature
 
 pub mod actions;
 mod client;
+mod connection;
 mod gen_macros;
+mod http;
+mod instrumentation;
+mod metrics;
+mod middleware;
 #[cfg(feature = "migrations")]
 pub mod migrations;
 #[cfg(feature = "mocking")]
@@ -33,6 +38,7 @@ pub use psl;
 pub use query_core;
 pub use query_core::{schema, Selection};
 pub use serde_json;
+pub use serde_value;
 use thiserror::Error;
 #[cfg(feature = "migrations")]
 pub use tokio;
@@ -40,6 +46,10 @@ pub use user_facing_errors as prisma_errors;
 
 pub use actions::*;
 pub use client::*;
+pub use connection::*;
+pub use http::*;
+pub use metrics::*;
+pub use middleware::*;
 #[cfg(feature = "mocking")]
 pub use mock::*;
 pub use operator::Operator;
@@ -139,3 +149,34 @@ pub fn merge_fields(fields: Vec<(String, PrismaValue)>) -> Vec<(String, PrismaVa
 pub fn sel(name: &str) -> Selection {
     Selection::new(name, None, [], [])
 }
+
+/// Creates a `_count` selection that counts the records of each relation,
+/// only including records that match the relation's filters.
+pub fn relation_count_selection(
+    relations: impl IntoIterator<Item = (&'static str, Vec<SerializedWhereInput>)>,
+) -> Selection {
+    Selection::new(
+        "_count",
+        None,
+        [],
+        relations
+            .into_iter()
+            .map(|(relation, where_params)| {
+                let arguments = (!where_params.is_empty())
+                    .then(|| {
+                        (
+                            "where".to_string(),
+                            prisma_models::PrismaValue::from(PrismaValue::Object(merge_fields(
+                                where_params.into_iter().map(Into::into).collect(),
+                            )))
+                            .into(),
+                        )
+                    })
+                    .into_iter()
+                    .collect::<Vec<_>>();
+
+                Selection::new(relation, None, arguments, [])
+            })
+            .collect::<Vec<_>>(),
+    )
+}
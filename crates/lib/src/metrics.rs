@@ -0,0 +1,281 @@
+use std::{collections::BTreeMap, fmt::Write, sync::Mutex, time::Duration};
+
+use crate::{QueryContext, QueryError};
+
+/// Upper bounds of the query duration histogram's buckets, in seconds.
+pub const DURATION_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    /// The number of observations less than or equal to each of `DURATION_BUCKETS`.
+    pub buckets: Vec<u64>,
+    pub sum: f64,
+    pub count: u64,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self {
+            buckets: vec![0; DURATION_BUCKETS.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+}
+
+impl Histogram {
+    fn observe(&mut self, value: f64) {
+        for (bucket, bound) in self.buckets.iter_mut().zip(DURATION_BUCKETS) {
+            if value <= *bound {
+                *bucket += 1;
+            }
+        }
+
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueryMetrics {
+    pub count: u64,
+    pub errors: u64,
+    /// Query durations in seconds.
+    /// Queries executed in a batch are recorded with the duration of the whole batch.
+    pub duration: Histogram,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransactionMetrics {
+    pub in_flight: i64,
+    pub committed: u64,
+    pub rolled_back: u64,
+}
+
+/// A snapshot of the metrics collected by a client, returned by `PrismaClient::_metrics`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Metrics {
+    /// Query metrics keyed by model and action.
+    /// Raw queries have an empty model and their operation name as the action.
+    pub queries: BTreeMap<(String, String), QueryMetrics>,
+    /// The number of failed queries, keyed by Prisma error code,
+    /// or `serialize`, `deserialize`, `middleware`, `disconnected` or `timeout` for errors that didn't come from Prisma.
+    pub errors: BTreeMap<String, u64>,
+    pub transactions: TransactionMetrics,
+}
+
+impl Metrics {
+    /// Renders the metrics in the Prometheus text exposition format.
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+
+        header(
+            &mut out,
+            "prisma_client_queries_total",
+            "The number of queries executed.",
+            "counter",
+        );
+        for ((model, action), metrics) in &self.queries {
+            let labels = query_labels(model, action);
+            writeln!(
+                out,
+                "prisma_client_queries_total{{{labels}}} {}",
+                metrics.count
+            )
+            .unwrap();
+        }
+
+        header(
+            &mut out,
+            "prisma_client_query_failures_total",
+            "The number of queries that failed.",
+            "counter",
+        );
+        for ((model, action), metrics) in &self.queries {
+            let labels = query_labels(model, action);
+            writeln!(
+                out,
+                "prisma_client_query_failures_total{{{labels}}} {}",
+                metrics.errors
+            )
+            .unwrap();
+        }
+
+        header(
+            &mut out,
+            "prisma_client_query_duration_seconds",
+            "How long queries took to execute.",
+            "histogram",
+        );
+        for ((model, action), metrics) in &self.queries {
+            let labels = query_labels(model, action);
+            let histogram = &metrics.duration;
+
+            for (bound, count) in DURATION_BUCKETS.iter().zip(&histogram.buckets) {
+                writeln!(
+                    out,
+                    "prisma_client_query_duration_seconds_bucket{{{labels},le=\"{bound}\"}} {count}"
+                )
+                .unwrap();
+            }
+            writeln!(
+                out,
+                "prisma_client_query_duration_seconds_bucket{{{labels},le=\"+Inf\"}} {}",
+                histogram.count
+            )
+            .unwrap();
+            writeln!(
+                out,
+                "prisma_client_query_duration_seconds_sum{{{labels}}} {}",
+                histogram.sum
+            )
+            .unwrap();
+            writeln!(
+                out,
+                "prisma_client_query_duration_seconds_count{{{labels}}} {}",
+                histogram.count
+            )
+            .unwrap();
+        }
+
+        header(
+            &mut out,
+            "prisma_client_errors_total",
+            "The number of query errors by error code.",
+            "counter",
+        );
+        for (code, count) in &self.errors {
+            writeln!(
+                out,
+                "prisma_client_errors_total{{code=\"{}\"}} {count}",
+                escape_label(code)
+            )
+            .unwrap();
+        }
+
+        header(
+            &mut out,
+            "prisma_client_transactions_in_flight",
+            "The number of open transactions.",
+            "gauge",
+        );
+        writeln!(
+            out,
+            "prisma_client_transactions_in_flight {}",
+            self.transactions.in_flight
+        )
+        .unwrap();
+
+        header(
+            &mut out,
+            "prisma_client_transactions_total",
+            "The number of finished transactions.",
+            "counter",
+        );
+        writeln!(
+            out,
+            "prisma_client_transactions_total{{outcome=\"committed\"}} {}",
+            self.transactions.committed
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "prisma_client_transactions_total{{outcome=\"rolled_back\"}} {}",
+            self.transactions.rolled_back
+        )
+        .unwrap();
+
+        out
+    }
+}
+
+fn header(out: &mut String, name: &str, help: &str, typ: &str) {
+    writeln!(out, "# HELP {name} {help}").unwrap();
+    writeln!(out, "# TYPE {name} {typ}").unwrap();
+}
+
+fn query_labels(model: &str, action: &str) -> String {
+    format!(
+        "model=\"{}\",action=\"{}\"",
+        escape_label(model),
+        escape_label(action)
+    )
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// The model and action a query's metrics are recorded under.
+pub(crate) fn query_key(ctx: &QueryContext) -> (String, String) {
+    (
+        ctx.model.clone().unwrap_or_default(),
+        ctx.action
+            .map(|action| action.name().to_string())
+            .unwrap_or_else(|| ctx.operation.name().to_string()),
+    )
+}
+
+fn error_code(error: &QueryError) -> String {
+    match error {
+        QueryError::Execute(error) | QueryError::RecordNotFound(error) => error
+            .as_known()
+            .map(|error| error.error_code.to_string())
+            .unwrap_or_else(|| "unknown".to_string()),
+        QueryError::Serialize(_) => "serialize".to_string(),
+        QueryError::Deserialize(_) => "deserialize".to_string(),
+        QueryError::Middleware(_) => "middleware".to_string(),
+        QueryError::Disconnected => "disconnected".to_string(),
+        QueryError::Timeout(_) => "timeout".to_string(),
+        QueryError::NestedTransactionUnsupported(_) => "nested_transaction".to_string(),
+        QueryError::InvalidArgument(_) => "invalid_argument".to_string(),
+    }
+}
+
+/// Collects metrics for a client and all of its transactions.
+#[derive(Default)]
+pub(crate) struct MetricsRecorder(Mutex<Metrics>);
+
+impl MetricsRecorder {
+    pub fn snapshot(&self) -> Metrics {
+        self.0.lock().unwrap().clone()
+    }
+
+    pub fn record_query(
+        &self,
+        key: (String, String),
+        duration: Duration,
+        error: Option<&QueryError>,
+    ) {
+        let mut metrics = self.0.lock().unwrap();
+
+        let query = metrics.queries.entry(key).or_default();
+        query.count += 1;
+        query.duration.observe(duration.as_secs_f64());
+
+        if let Some(error) = error {
+            query.errors += 1;
+            *metrics.errors.entry(error_code(error)).or_default() += 1;
+        }
+    }
+
+    pub fn transaction_started(&self) {
+        self.0.lock().unwrap().transactions.in_flight += 1;
+    }
+
+    pub fn transaction_finished(&self, committed: bool) {
+        let mut metrics = self.0.lock().unwrap();
+        let transactions = &mut metrics.transactions;
+
+        transactions.in_flight -= 1;
+        match committed {
+            true => transactions.committed += 1,
+            false => transactions.rolled_back += 1,
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use std::{sync::Arc, time::Duration};
+
+use futures::future::BoxFuture;
+use query_core::Operation;
+
+use crate::{ExecutionEngine, ModelOperation, ModelWriteOperation, Result};
+
+/// Information about an operation that is about to be sent to the query engine.
+#[derive(Debug, Clone)]
+pub struct QueryContext {
+    /// The model being queried, or `None` for raw queries.
+    pub model: Option<String>,
+    /// The action being performed on `model`, or `None` for raw queries.
+    pub action: Option<ModelOperation>,
+    /// The operation that will be executed, including its arguments and selection.
+    /// Modifying it will change the query that is executed.
+    pub operation: Operation,
+}
+
+impl QueryContext {
+    pub(crate) fn new(
+        operation: Operation,
+        context: Option<(&'static str, ModelOperation)>,
+    ) -> Self {
+        Self {
+            model: context.map(|(model, _)| model.to_string()),
+            action: context.map(|(_, action)| action),
+            operation,
+        }
+    }
+
+    pub(crate) fn mutation(&self) -> Option<(String, ModelWriteOperation)> {
+        match (&self.model, self.action) {
+            (Some(model), Some(ModelOperation::Write(action))) => Some((model.clone(), action)),
+            _ => None,
+        }
+    }
+}
+
+/// A layer that is run around every operation the client executes.
+///
+/// Middleware is registered with `PrismaClientBuilder::with_middleware` and runs in the
+/// order it was registered, with each one deciding whether to call the next.
+/// Not calling `next` short-circuits the query and returns the middleware's result instead.
+///
+/// Results are the raw values returned by the query engine, before they are
+/// deserialized into the query's return type.
+pub trait Middleware: Send + Sync + 'static {
+    fn handle<'a>(
+        &'a self,
+        ctx: QueryContext,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<serde_value::Value>>;
+
+    /// Called instead of `handle` for queries executed together using `_batch`,
+    /// since they are sent to the query engine at once.
+    /// By default, the batch is passed to the next middleware unchanged.
+    fn handle_batch<'a>(
+        &'a self,
+        ctxs: Vec<QueryContext>,
+        next: NextBatch<'a>,
+    ) -> BoxFuture<'a, Result<Vec<Result<serde_value::Value>>>> {
+        next.run(ctxs)
+    }
+}
+
+pub(crate) type MiddlewareStack = Arc<Vec<Box<dyn Middleware>>>;
+
+/// The rest of the middleware chain, ending with the query engine.
+pub struct Next<'a> {
+    pub(crate) engine: &'a ExecutionEngine,
+    pub(crate) middleware: &'a [Box<dyn Middleware>],
+    pub(crate) timeout: Option<Duration>,
+}
+
+impl<'a> Next<'a> {
+    pub fn run(self, ctx: QueryContext) -> BoxFuture<'a, Result<serde_value::Value>> {
+        match self.middleware.split_first() {
+            Some((first, rest)) => first.handle(
+                ctx,
+                Next {
+                    engine: self.engine,
+                    middleware: rest,
+                    timeout: self.timeout,
+                },
+            ),
+            None => Box::pin(self.engine.execute(ctx.operation, self.timeout)),
+        }
+    }
+}
+
+/// The rest of the middleware chain for a batch, ending with the query engine.
+pub struct NextBatch<'a> {
+    pub(crate) engine: &'a ExecutionEngine,
+    pub(crate) middleware: &'a [Box<dyn Middleware>],
+    pub(crate) isolation_level: Option<String>,
+    pub(crate) timeout: Option<Duration>,
+}
+
+impl<'a> NextBatch<'a> {
+    pub fn run(
+        self,
+        ctxs: Vec<QueryContext>,
+    ) -> BoxFuture<'a, Result<Vec<Result<serde_value::Value>>>> {
+        match self.middleware.split_first() {
+            Some((first, rest)) => first.handle_batch(
+                ctxs,
+                NextBatch {
+                    engine: self.engine,
+                    middleware: rest,
+                    isolation_level: self.isolation_level,
+                    timeout: self.timeout,
+                },
+            ),
+            None => Box::pin(self.engine.execute_all(
+                ctxs.into_iter().map(|ctx| ctx.operation).collect(),
+                self.isolation_level,
+                self.timeout,
+            )),
+        }
+    }
+}
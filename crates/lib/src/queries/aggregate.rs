@@ -0,0 +1,223 @@
+use std::time::Duration;
+
+use indexmap::IndexMap;
+use query_core::{Operation, Selection};
+
+use crate::{
+    merge_fields, sel, ModelOperation, ModelQuery, ModelReadOperation, ModelTypes, OrderByQuery,
+    PaginatedQuery, PrismaClientInternals, PrismaValue, Query, QueryConvert, TimeoutQuery,
+    WhereInput, WhereQuery,
+};
+
+/// The aggregate functions supported by the query engine.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AggregateFunction {
+    Count,
+    Avg,
+    Sum,
+    Min,
+    Max,
+}
+
+impl AggregateFunction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Count => "_count",
+            Self::Avg => "_avg",
+            Self::Sum => "_sum",
+            Self::Min => "_min",
+            Self::Max => "_max",
+        }
+    }
+}
+
+/// Groups aggregate params by their function, producing a selection for each function
+/// that contains the fields to aggregate, eg. `_avg { views likes }`.
+pub fn aggregate_selections<P: Into<(AggregateFunction, String)>>(
+    params: impl IntoIterator<Item = P>,
+) -> Vec<Selection> {
+    let mut grouped = IndexMap::<AggregateFunction, Vec<Selection>>::new();
+
+    for (function, field) in params.into_iter().map(Into::into) {
+        grouped
+            .entry(function)
+            .or_insert_with(Vec::new)
+            .push(sel(&field));
+    }
+
+    grouped
+        .into_iter()
+        .map(|(function, fields)| Selection::new(function.name(), None, [], fields))
+        .collect()
+}
+
+pub struct Aggregate<'a, Actions: ModelTypes> {
+    client: &'a PrismaClientInternals,
+    timeout: Option<Duration>,
+    pub where_params: Vec<Actions::Where>,
+    pub order_by_params: Vec<Actions::OrderBy>,
+    pub cursor_params: Vec<Actions::Cursor>,
+    pub skip: Option<i64>,
+    pub take: Option<i64>,
+    pub aggregate_params: Vec<Actions::Aggregate>,
+}
+
+impl<'a, Actions: ModelTypes> Aggregate<'a, Actions> {
+    pub fn new(client: &'a PrismaClientInternals, where_params: Vec<Actions::Where>) -> Self {
+        Self {
+            client,
+            timeout: None,
+            where_params,
+            order_by_params: vec![],
+            cursor_params: vec![],
+            skip: None,
+            take: None,
+            aggregate_params: vec![],
+        }
+    }
+
+    pub fn select(mut self, params: Vec<Actions::Aggregate>) -> Self {
+        self.aggregate_params.extend(params);
+        self
+    }
+
+    pub fn order_by(mut self, param: Actions::OrderBy) -> Self {
+        self.order_by_params.push(param);
+        self
+    }
+
+    pub fn cursor(mut self, param: Actions::Cursor) -> Self {
+        self.cursor_params.push(param);
+        self
+    }
+
+    pub fn skip(mut self, skip: i64) -> Self {
+        self.skip = Some(skip);
+        self
+    }
+
+    pub fn take(mut self, take: i64) -> Self {
+        self.take = Some(take);
+        self
+    }
+
+    pub async fn exec(self) -> super::Result<Actions::AggregateResult> {
+        super::exec(self).await
+    }
+}
+
+impl<'a, Actions: ModelTypes> QueryConvert for Aggregate<'a, Actions> {
+    type RawType = Actions::AggregateResult;
+    type ReturnValue = Self::RawType;
+
+    fn convert(raw: Self::RawType) -> super::Result<Self::ReturnValue> {
+        Ok(raw)
+    }
+}
+
+impl<'a, Actions: ModelTypes> Query<'a> for Aggregate<'a, Actions> {
+    fn graphql(self) -> (Operation, &'a PrismaClientInternals) {
+        // The engine requires at least one aggregate to be selected
+        let nested_selections = match self.aggregate_params.is_empty() {
+            true => vec![Selection::new(
+                AggregateFunction::Count.name(),
+                None,
+                [],
+                [sel("_all")],
+            )],
+            false => aggregate_selections(self.aggregate_params),
+        };
+
+        (
+            Operation::Read(Self::base_selection(
+                [
+                    (!self.where_params.is_empty()).then(|| {
+                        (
+                            "where".to_string(),
+                            PrismaValue::Object(merge_fields(
+                                self.where_params
+                                    .into_iter()
+                                    .map(WhereInput::serialize)
+                                    .map(|s| (s.field, s.value.into()))
+                                    .collect(),
+                            )),
+                        )
+                    }),
+                    (!self.order_by_params.is_empty()).then(|| {
+                        (
+                            "orderBy".to_string(),
+                            PrismaValue::List(
+                                self.order_by_params
+                                    .into_iter()
+                                    .map(|p| PrismaValue::Object(vec![p.into()]))
+                                    .collect(),
+                            ),
+                        )
+                    }),
+                    (!self.cursor_params.is_empty()).then(|| {
+                        (
+                            "cursor".to_string(),
+                            PrismaValue::Object(
+                                self.cursor_params
+                                    .into_iter()
+                                    .map(WhereInput::serialize)
+                                    .map(|s| (s.field, s.value.into()))
+                                    .collect(),
+                            ),
+                        )
+                    }),
+                    self.skip
+                        .map(|skip| ("skip".to_string(), PrismaValue::Int(skip as i32))),
+                    self.take
+                        .map(|take| ("take".to_string(), PrismaValue::Int(take as i32))),
+                ]
+                .into_iter()
+                .flatten(),
+                nested_selections,
+            )),
+            self.client,
+        )
+    }
+
+    fn context(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
+}
+
+impl<'a, Actions: ModelTypes> TimeoutQuery<'a> for Aggregate<'a, Actions> {
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
+}
+
+impl<'a, Actions: ModelTypes> ModelQuery<'a> for Aggregate<'a, Actions> {
+    type Types = Actions;
+
+    const TYPE: ModelOperation = ModelOperation::Read(ModelReadOperation::Aggregate);
+}
+
+impl<'a, Actions: ModelTypes> WhereQuery<'a> for Aggregate<'a, Actions> {
+    fn add_where(&mut self, param: Actions::Where) {
+        self.where_params.push(param);
+    }
+}
+
+impl<'a, Actions: ModelTypes> OrderByQuery<'a> for Aggregate<'a, Actions> {
+    fn add_order_by(&mut self, param: Actions::OrderBy) {
+        self.order_by_params.push(param);
+    }
+}
+
+impl<'a, Actions: ModelTypes> PaginatedQuery<'a> for Aggregate<'a, Actions> {
+    fn add_cursor(&mut self, param: Actions::Cursor) {
+        self.cursor_params.push(param);
+    }
+
+    fn set_skip(&mut self, skip: i64) {
+        self.skip = Some(skip);
+    }
+
+    fn set_take(&mut self, take: i64) {
+        self.take = Some(take);
+    }
+}
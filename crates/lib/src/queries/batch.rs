@@ -1,8 +1,6 @@
-use std::{collections::VecDeque, num::NonZeroUsize};
+use std::{collections::VecDeque, num::NonZeroUsize, time::Duration};
 
-use query_core::Operation;
-
-use crate::{PrismaClientInternals, Query, QueryConvert};
+use crate::{PrismaClientInternals, Query, QueryContext, QueryConvert, TransactionIsolationLevel};
 
 pub enum VecMeta {
     Empty,
@@ -16,7 +14,7 @@ pub enum BatchItemDataMeta {
 }
 
 pub enum BatchItemData {
-    Query(Operation),
+    Query(QueryContext),
     Vec(Vec<Self>),
     Tuple(Vec<Self>),
 }
@@ -34,9 +32,9 @@ impl BatchItemData {
         }
     }
 
-    fn operations(self, v: &mut Vec<Operation>) {
+    fn operations(self, v: &mut Vec<QueryContext>) {
         match self {
-            Self::Query(op) => v.push(op),
+            Self::Query(ctx) => v.push(ctx),
             Self::Vec(items) => items.into_iter().for_each(|i| i.operations(v)),
             Self::Tuple(items) => items.into_iter().for_each(|i| i.operations(v)),
         }
@@ -65,7 +63,7 @@ impl BatchData {
         }
     }
 
-    fn operations(self) -> Vec<Operation> {
+    fn operations(self) -> Vec<QueryContext> {
         let items = match self {
             Self::Tuple(items) => items,
             Self::Iterator(items) => items,
@@ -79,18 +77,52 @@ impl BatchData {
     }
 }
 
+#[derive(Default, Clone)]
+pub struct BatchOptions {
+    pub(crate) isolation_level: Option<String>,
+    pub(crate) timeout: Option<Duration>,
+}
+
+impl BatchOptions {
+    /// The isolation level of the batch's transaction.
+    /// Ignored if the batch is run in an interactive transaction,
+    /// since it uses the interactive transaction's isolation level.
+    pub fn with_isolation_level(self, isolation_level: impl TransactionIsolationLevel) -> Self {
+        Self {
+            isolation_level: Some(isolation_level.to_string()),
+            ..self
+        }
+    }
+
+    /// Cancels the batch if it takes longer than `timeout`,
+    /// overriding the client's default query timeout.
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        Self {
+            timeout: Some(timeout),
+            ..self
+        }
+    }
+}
+
 pub async fn batch<'batch, 'b, T: BatchContainer<'batch, Marker>, Marker>(
     container: T,
     client: &'b PrismaClientInternals,
+) -> super::Result<<T as BatchContainer<'batch, Marker>>::ReturnType> {
+    batch_with_options(container, client, BatchOptions::default()).await
+}
+
+pub async fn batch_with_options<'batch, 'b, T: BatchContainer<'batch, Marker>, Marker>(
+    container: T,
+    client: &'b PrismaClientInternals,
+    options: BatchOptions,
 ) -> super::Result<<T as BatchContainer<'batch, Marker>>::ReturnType> {
     let data = container.data();
     let meta = data.meta();
 
-    let operations = data.operations();
+    let ctxs = data.operations();
 
     let values = client
-        .engine
-        .execute_all(operations)
+        .execute_all(ctxs, options.isolation_level, options.timeout)
         .await?
         .into_iter()
         .collect::<super::Result<VecDeque<_>>>()?;
@@ -117,7 +149,9 @@ impl<Q: QueryConvert> BatchItemParent for Q {
 
 impl<'a, 'b, Q: Query<'a>> BatchItem<'b> for Q {
     fn data(self) -> BatchItemData {
-        BatchItemData::Query(self.graphql().0)
+        let context = self.context();
+
+        BatchItemData::Query(QueryContext::new(self.graphql().0, context))
     }
 
     fn resolve(
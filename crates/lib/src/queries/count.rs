@@ -1,14 +1,17 @@
+use std::time::Duration;
+
 use query_core::{Operation, Selection};
 use serde::Deserialize;
 
 use crate::{
     merge_fields, ModelOperation, ModelQuery, ModelReadOperation, ModelTypes, OrderByQuery,
-    PaginatedQuery, PrismaClientInternals, PrismaValue, Query, QueryConvert, WhereInput,
-    WhereQuery,
+    PaginatedQuery, PrismaClientInternals, PrismaValue, Query, QueryConvert, TimeoutQuery,
+    WhereInput, WhereQuery,
 };
 
 pub struct Count<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
+    timeout: Option<Duration>,
     pub where_params: Vec<Actions::Where>,
     pub order_by_params: Vec<Actions::OrderBy>,
     pub cursor_params: Vec<Actions::Cursor>,
@@ -20,6 +23,7 @@ impl<'a, Actions: ModelTypes> Count<'a, Actions> {
     pub fn new(client: &'a PrismaClientInternals, where_params: Vec<Actions::Where>) -> Self {
         Self {
             client,
+            timeout: None,
             where_params,
             order_by_params: vec![],
             cursor_params: vec![],
@@ -131,6 +135,16 @@ impl<'a, Actions: ModelTypes> Query<'a> for Count<'a, Actions> {
             self.client,
         )
     }
+
+    fn context(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
+}
+
+impl<'a, Actions: ModelTypes> TimeoutQuery<'a> for Count<'a, Actions> {
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for Count<'a, Actions> {
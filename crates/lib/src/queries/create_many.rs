@@ -1,12 +1,15 @@
+use std::time::Duration;
+
 use query_core::{Operation, Selection};
 
 use crate::{
     merge_fields, BatchResult, ModelOperation, ModelQuery, ModelTypes, ModelWriteOperation,
-    PrismaClientInternals, PrismaValue, Query, QueryConvert,
+    PrismaClientInternals, PrismaValue, Query, QueryConvert, TimeoutQuery,
 };
 
 pub struct CreateMany<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
+    timeout: Option<Duration>,
     pub set_params: Vec<Vec<Actions::UncheckedSet>>,
     pub skip_duplicates: bool,
 }
@@ -18,6 +21,7 @@ impl<'a, Actions: ModelTypes> CreateMany<'a, Actions> {
     ) -> Self {
         Self {
             client,
+            timeout: None,
             set_params,
             skip_duplicates: false,
         }
@@ -87,6 +91,16 @@ impl<'a, Actions: ModelTypes> Query<'a> for CreateMany<'a, Actions> {
             self.client,
         )
     }
+
+    fn context(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
+}
+
+impl<'a, Actions: ModelTypes> TimeoutQuery<'a> for CreateMany<'a, Actions> {
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for CreateMany<'a, Actions> {
@@ -1,13 +1,16 @@
+use std::time::Duration;
+
 use query_core::{Operation, Selection};
 
 use crate::{
     merge_fields, Include, IncludeType, ModelOperation, ModelQuery, ModelTypes,
     ModelWriteOperation, PrismaClientInternals, PrismaValue, Query, QueryConvert, Select,
-    SelectType, UncheckedSetQuery, WithQuery,
+    SelectType, TimeoutQuery, UncheckedSetQuery, WithQuery,
 };
 
 pub struct CreateUnchecked<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
+    timeout: Option<Duration>,
     pub set_params: Vec<Actions::UncheckedSet>,
     pub with_params: Vec<Actions::With>,
 }
@@ -16,6 +19,7 @@ impl<'a, Actions: ModelTypes> CreateUnchecked<'a, Actions> {
     pub fn new(client: &'a PrismaClientInternals, set_params: Vec<Actions::UncheckedSet>) -> Self {
         Self {
             client,
+            timeout: None,
             set_params,
             with_params: vec![],
         }
@@ -50,6 +54,8 @@ impl<'a, Actions: ModelTypes> CreateUnchecked<'a, Actions> {
             self.client,
             Operation::Write(Self::to_selection(self.set_params, select.to_selections())),
         )
+        .with_timeout(self.timeout)
+        .for_model_query::<Self>()
     }
 
     pub fn include<I: IncludeType<ModelData = Actions::Data>>(
@@ -60,6 +66,8 @@ impl<'a, Actions: ModelTypes> CreateUnchecked<'a, Actions> {
             self.client,
             Operation::Write(Self::to_selection(self.set_params, include.to_selections())),
         )
+        .with_timeout(self.timeout)
+        .for_model_query::<Self>()
     }
 
     pub async fn exec(self) -> super::Result<Actions::Data> {
@@ -87,6 +95,16 @@ impl<'a, Actions: ModelTypes> Query<'a> for CreateUnchecked<'a, Actions> {
             self.client,
         )
     }
+
+    fn context(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
+}
+
+impl<'a, Actions: ModelTypes> TimeoutQuery<'a> for CreateUnchecked<'a, Actions> {
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for CreateUnchecked<'a, Actions> {
@@ -1,13 +1,16 @@
+use std::time::Duration;
+
 use query_core::{Operation, Selection};
 
 use crate::{
     Include, IncludeType, ModelOperation, ModelQuery, ModelTypes, ModelWriteOperation,
-    PrismaClientInternals, PrismaValue, Query, QueryConvert, Select, SelectType, WhereInput,
-    WithQuery,
+    PrismaClientInternals, PrismaValue, Query, QueryConvert, Select, SelectType, TimeoutQuery,
+    WhereInput, WithQuery,
 };
 
 pub struct Delete<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
+    timeout: Option<Duration>,
     pub where_param: Actions::WhereUnique,
     pub with_params: Vec<Actions::With>,
 }
@@ -20,6 +23,7 @@ impl<'a, Actions: ModelTypes> Delete<'a, Actions> {
     ) -> Self {
         Self {
             client,
+            timeout: None,
             where_param,
             with_params,
         }
@@ -51,6 +55,8 @@ impl<'a, Actions: ModelTypes> Delete<'a, Actions> {
             self.client,
             Operation::Write(Self::to_selection(self.where_param, select.to_selections())),
         )
+        .with_timeout(self.timeout)
+        .for_model_query::<Self>()
     }
 
     pub fn include<I: IncludeType<ModelData = Actions::Data>>(
@@ -61,6 +67,8 @@ impl<'a, Actions: ModelTypes> Delete<'a, Actions> {
             self.client,
             Operation::Write(Self::to_selection(self.where_param, select.to_selections())),
         )
+        .with_timeout(self.timeout)
+        .for_model_query::<Self>()
     }
 
     pub async fn exec(self) -> super::Result<Actions::Data> {
@@ -88,6 +96,16 @@ impl<'a, Actions: ModelTypes> Query<'a> for Delete<'a, Actions> {
             self.client,
         )
     }
+
+    fn context(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
+}
+
+impl<'a, Actions: ModelTypes> TimeoutQuery<'a> for Delete<'a, Actions> {
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for Delete<'a, Actions> {
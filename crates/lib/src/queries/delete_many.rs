@@ -1,12 +1,15 @@
+use std::time::Duration;
+
 use query_core::Operation;
 
 use crate::{
     merge_fields, BatchResult, ModelOperation, ModelQuery, ModelTypes, ModelWriteOperation,
-    PrismaClientInternals, PrismaValue, Query, QueryConvert, WhereInput, WhereQuery,
+    PrismaClientInternals, PrismaValue, Query, QueryConvert, TimeoutQuery, WhereInput, WhereQuery,
 };
 
 pub struct DeleteMany<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
+    timeout: Option<Duration>,
     pub where_params: Vec<Actions::Where>,
 }
 
@@ -14,6 +17,7 @@ impl<'a, Actions: ModelTypes> DeleteMany<'a, Actions> {
     pub fn new(client: &'a PrismaClientInternals, where_params: Vec<Actions::Where>) -> Self {
         Self {
             client,
+            timeout: None,
             where_params,
         }
     }
@@ -59,6 +63,16 @@ impl<'a, Actions: ModelTypes> Query<'a> for DeleteMany<'a, Actions> {
             self.client,
         )
     }
+
+    fn context(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
+}
+
+impl<'a, Actions: ModelTypes> TimeoutQuery<'a> for DeleteMany<'a, Actions> {
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for DeleteMany<'a, Actions> {
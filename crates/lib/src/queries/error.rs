@@ -1,23 +1,261 @@
+use std::{ops::Deref, time::Duration};
+
 use serde::Serialize;
 use thiserror::Error;
-use user_facing_errors::UserFacingError;
+use user_facing_errors::{query_engine::RecordRequiredButNotFound, UserFacingError};
+
+use crate::{ModelOperation, ModelReadOperation};
+
+/// An error produced by the query engine, along with the query that caused it.
+/// Dereferences to the underlying `user_facing_errors::Error`.
+#[derive(Debug, Serialize)]
+#[serde(transparent)]
+pub struct EngineError {
+    error: user_facing_errors::Error,
+    /// The model that was being queried, or `None` for raw queries and transactions.
+    #[serde(skip)]
+    pub model: Option<String>,
+    /// The action that was being performed, or `None` for raw queries and transactions.
+    #[serde(skip)]
+    pub action: Option<ModelOperation>,
+}
+
+impl EngineError {
+    pub fn into_inner(self) -> user_facing_errors::Error {
+        self.error
+    }
+}
+
+impl Deref for EngineError {
+    type Target = user_facing_errors::Error;
+
+    fn deref(&self) -> &Self::Target {
+        &self.error
+    }
+}
+
+impl From<user_facing_errors::Error> for EngineError {
+    fn from(error: user_facing_errors::Error) -> Self {
+        Self {
+            error,
+            model: None,
+            action: None,
+        }
+    }
+}
+
+/// A typed version of the known Prisma errors that are commonly handled,
+/// parsed from an error's code and metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// P2002: A unique constraint failed.
+    /// `target` contains the constraint's fields, or its name for some databases.
+    UniqueConstraintViolation { target: Vec<String> },
+    /// P2003: A foreign key constraint failed on `field`.
+    ForeignKeyViolation { field: Option<String> },
+    /// P2011: A null value was provided for a required field.
+    NullConstraintViolation { constraint: Option<String> },
+    /// P2000, P2005-P2007, P2009, P2012, P2013, P2019, P2020:
+    /// A value or argument provided to the query was invalid.
+    Validation,
+    /// P2001, P2025: A record that the query required does not exist.
+    RecordNotFound,
+    /// P2034: A transaction failed due to a write conflict or deadlock and can be retried.
+    TransactionConflict,
+    /// P1008, P2024: The query or acquiring a connection timed out,
+    /// or the query took longer than its timeout.
+    Timeout,
+    /// P1001, P1002, P1017: The database could not be reached or closed the connection,
+    /// or the client has been disconnected.
+    ConnectionFailed,
+    /// A known Prisma error that doesn't have its own kind.
+    Other { code: String },
+    /// An error without a Prisma error code,
+    /// including serialization and middleware errors.
+    Unknown,
+}
+
+impl ErrorKind {
+    /// A snake_case name for the kind, eg. `unique_constraint_violation`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::UniqueConstraintViolation { .. } => "unique_constraint_violation",
+            Self::ForeignKeyViolation { .. } => "foreign_key_violation",
+            Self::NullConstraintViolation { .. } => "null_constraint_violation",
+            Self::Validation => "validation",
+            Self::RecordNotFound => "record_not_found",
+            Self::TransactionConflict => "transaction_conflict",
+            Self::Timeout => "timeout",
+            Self::ConnectionFailed => "connection_failed",
+            Self::Other { .. } => "other",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    fn from_engine_error(error: &user_facing_errors::Error) -> Self {
+        let known = match error.as_known() {
+            Some(known) => known,
+            None => return Self::Unknown,
+        };
+
+        let meta_string = |key: &str| {
+            known
+                .meta
+                .get(key)
+                .and_then(|value| value.as_str())
+                .map(ToString::to_string)
+        };
+
+        match known.error_code.as_ref() {
+            "P2002" => Self::UniqueConstraintViolation {
+                target: match known.meta.get("target") {
+                    Some(serde_json::Value::Array(fields)) => fields
+                        .iter()
+                        .filter_map(|field| field.as_str().map(ToString::to_string))
+                        .collect(),
+                    Some(serde_json::Value::String(index)) => vec![index.to_string()],
+                    _ => vec![],
+                },
+            },
+            "P2003" => Self::ForeignKeyViolation {
+                field: meta_string("field_name"),
+            },
+            "P2011" => Self::NullConstraintViolation {
+                constraint: meta_string("constraint"),
+            },
+            "P2000" | "P2005" | "P2006" | "P2007" | "P2009" | "P2012" | "P2013" | "P2019"
+            | "P2020" => Self::Validation,
+            "P2001" | "P2025" => Self::RecordNotFound,
+            "P2034" => Self::TransactionConflict,
+            "P1008" | "P2024" => Self::Timeout,
+            "P1001" | "P1002" | "P1017" => Self::ConnectionFailed,
+            code => Self::Other {
+                code: code.to_string(),
+            },
+        }
+    }
+}
 
 #[derive(Debug, Error, Serialize)]
 pub enum QueryError {
     #[error("Error executing query: {} - {}", .0.as_known().map(|k| k.error_code.to_string()).unwrap_or("Unknown".to_string()), .0.message())]
-    Execute(user_facing_errors::Error),
+    Execute(EngineError),
+
+    #[error("Record not found: {}", .0.message())]
+    RecordNotFound(EngineError),
 
     #[error("Error serializing query result: {0}")]
     Serialize(String),
 
     #[error("Error deserializing query result into return type: {0}")]
     Deserialize(String),
+
+    /// Returned by a [`Middleware`](crate::Middleware) that rejected a query.
+    #[error("Query rejected by middleware: {0}")]
+    Middleware(String),
+
+    /// Returned for queries made after the client was disconnected with `_disconnect`.
+    #[error("The client has been disconnected")]
+    Disconnected,
+
+    /// Returned for queries that took longer than their timeout.
+    /// The database may still have finished executing them.
+    #[error("Query timed out after {0:?}")]
+    Timeout(Duration),
+
+    /// Returned when starting a transaction inside another on a connector without savepoints.
+    #[error("Nested transactions are not supported by {0}")]
+    NestedTransactionUnsupported(&'static str),
+
+    /// Returned for arguments that are rejected before the query is executed,
+    /// such as a page size that isn't positive.
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
 }
 
 impl QueryError {
+    /// Converts an error produced by the engine while executing a query.
+    pub(crate) fn from_engine(error: impl Into<user_facing_errors::Error>) -> Self {
+        Self::Execute(error.into().into())
+    }
+
+    /// Attaches the model and action of the query that caused the error,
+    /// separating out errors caused by an `*_or_throw` query not finding a record.
+    pub(crate) fn with_context(
+        self,
+        model: Option<String>,
+        action: Option<ModelOperation>,
+    ) -> Self {
+        match self {
+            Self::Execute(error) => {
+                let error = EngineError {
+                    model,
+                    action,
+                    ..error
+                };
+
+                let or_throw = matches!(
+                    action,
+                    Some(ModelOperation::Read(
+                        ModelReadOperation::FindUniqueOrThrow
+                            | ModelReadOperation::FindFirstOrThrow
+                    ))
+                );
+
+                let not_found = error
+                    .as_known()
+                    .map(|e| {
+                        e.error_code == <RecordRequiredButNotFound as UserFacingError>::ERROR_CODE
+                    })
+                    .unwrap_or(false);
+
+                match or_throw && not_found {
+                    true => Self::RecordNotFound(error),
+                    false => Self::Execute(error),
+                }
+            }
+            Self::RecordNotFound(error) => Self::RecordNotFound(EngineError {
+                model,
+                action,
+                ..error
+            }),
+            error => error,
+        }
+    }
+
+    /// The kind of error that occurred, for handling common Prisma errors without inspecting their metadata.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Execute(error) | Self::RecordNotFound(error) => {
+                ErrorKind::from_engine_error(error)
+            }
+            Self::Disconnected => ErrorKind::ConnectionFailed,
+            Self::Timeout(_) => ErrorKind::Timeout,
+            Self::InvalidArgument(_) => ErrorKind::Validation,
+            _ => ErrorKind::Unknown,
+        }
+    }
+
+    /// The model that was being queried when the error occurred, if known.
+    pub fn model(&self) -> Option<&str> {
+        match self {
+            Self::Execute(error) | Self::RecordNotFound(error) => error.model.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The action that was being performed when the error occurred, if known.
+    pub fn action(&self) -> Option<ModelOperation> {
+        match self {
+            Self::Execute(error) | Self::RecordNotFound(error) => error.action,
+            _ => None,
+        }
+    }
+
     pub fn is_prisma_error<T: UserFacingError>(&self) -> bool {
         match self {
-            Self::Execute(error) => error
+            Self::Execute(error) | Self::RecordNotFound(error) => error
                 .as_known()
                 .map(|e| e.error_code == <T as UserFacingError>::ERROR_CODE)
                 .unwrap_or(false),
@@ -1,11 +1,14 @@
+use std::time::Duration;
+
 use prisma_models::PrismaValue;
 use query_core::{Operation, Selection};
 use serde_json::Value;
 
-use crate::{raw::Raw, PrismaClientInternals, Query, QueryConvert};
+use crate::{raw::Raw, PrismaClientInternals, Query, QueryConvert, TimeoutQuery};
 
 pub struct ExecuteRaw<'a> {
     client: &'a PrismaClientInternals,
+    timeout: Option<Duration>,
     sql: String,
     params: Vec<Value>,
 }
@@ -16,6 +19,7 @@ impl<'a> ExecuteRaw<'a> {
 
         Self {
             client,
+            timeout: None,
             sql,
             params,
         }
@@ -54,3 +58,9 @@ impl<'a> Query<'a> for ExecuteRaw<'a> {
         )
     }
 }
+
+impl<'a> TimeoutQuery<'a> for ExecuteRaw<'a> {
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
+}
@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use query_core::Operation;
+use serde::de::IgnoredAny;
+
+use crate::{
+    merge_fields, sel, ModelOperation, ModelQuery, ModelReadOperation, ModelTypes,
+    PrismaClientInternals, PrismaValue, Query, QueryConvert, TimeoutQuery, WhereInput, WhereQuery,
+};
+
+/// Checks whether any records match a set of filters,
+/// only selecting the primary key of the first matching record.
+pub struct Exists<'a, Actions: ModelTypes> {
+    client: &'a PrismaClientInternals,
+    timeout: Option<Duration>,
+    pub where_params: Vec<Actions::Where>,
+}
+
+impl<'a, Actions: ModelTypes> Exists<'a, Actions> {
+    pub fn new(client: &'a PrismaClientInternals, where_params: Vec<Actions::Where>) -> Self {
+        Self {
+            client,
+            timeout: None,
+            where_params,
+        }
+    }
+
+    pub async fn exec(self) -> super::Result<bool> {
+        super::exec(self).await
+    }
+}
+
+impl<'a, Actions: ModelTypes> QueryConvert for Exists<'a, Actions> {
+    type RawType = Option<IgnoredAny>;
+    type ReturnValue = bool;
+
+    fn convert(raw: Self::RawType) -> super::Result<Self::ReturnValue> {
+        Ok(raw.is_some())
+    }
+}
+
+impl<'a, Actions: ModelTypes> Query<'a> for Exists<'a, Actions> {
+    fn graphql(self) -> (Operation, &'a PrismaClientInternals) {
+        (
+            Operation::Read(Self::base_selection(
+                (!self.where_params.is_empty()).then(|| {
+                    (
+                        "where".to_string(),
+                        PrismaValue::Object(merge_fields(
+                            self.where_params
+                                .into_iter()
+                                .map(WhereInput::serialize)
+                                .map(|s| (s.field, s.value.into()))
+                                .collect(),
+                        )),
+                    )
+                }),
+                Actions::PRIMARY_KEY.iter().map(|field| sel(field)),
+            )),
+            self.client,
+        )
+    }
+
+    fn context(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
+}
+
+impl<'a, Actions: ModelTypes> TimeoutQuery<'a> for Exists<'a, Actions> {
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
+}
+
+impl<'a, Actions: ModelTypes> ModelQuery<'a> for Exists<'a, Actions> {
+    type Types = Actions;
+
+    const TYPE: ModelOperation = ModelOperation::Read(ModelReadOperation::FindFirst);
+}
+
+impl<'a, Actions: ModelTypes> WhereQuery<'a> for Exists<'a, Actions> {
+    fn add_where(&mut self, param: Actions::Where) {
+        self.where_params.push(param);
+    }
+}
@@ -1,17 +1,22 @@
+use std::time::Duration;
+
 use query_core::{Operation, Selection};
 
 use crate::{
-    merge_fields, Include, IncludeType, ModelOperation, ModelQuery, ModelReadOperation, ModelTypes,
-    OrderByQuery, PaginatedQuery, PrismaClientInternals, PrismaValue, Query, QueryConvert, Select,
-    SelectType, WhereInput, WhereQuery, WithQuery,
+    merge_fields, DistinctQuery, Include, IncludeType, ModelOperation, ModelQuery,
+    ModelReadOperation, ModelTypes, OrderByQuery, PaginatedQuery, PrismaClientInternals,
+    PrismaValue, Query, QueryConvert, Select, SelectType, TimeoutQuery, WhereInput, WhereQuery,
+    WithQuery,
 };
 
 pub struct FindFirst<'a, Actions: ModelTypes> {
-    client: &'a PrismaClientInternals,
+    pub(crate) client: &'a PrismaClientInternals,
+    pub(crate) timeout: Option<Duration>,
     pub where_params: Vec<Actions::Where>,
     pub with_params: Vec<Actions::With>,
     pub order_by_params: Vec<Actions::OrderBy>,
     pub cursor_params: Vec<Actions::Cursor>,
+    pub distinct_params: Vec<Actions::ScalarField>,
     pub skip: Option<i64>,
     pub take: Option<i64>,
 }
@@ -20,10 +25,12 @@ impl<'a, Actions: ModelTypes> FindFirst<'a, Actions> {
     pub fn new(client: &'a PrismaClientInternals, where_params: Vec<Actions::Where>) -> Self {
         Self {
             client,
+            timeout: None,
             where_params,
             with_params: vec![],
             order_by_params: vec![],
             cursor_params: vec![],
+            distinct_params: vec![],
             skip: None,
             take: None,
         }
@@ -44,6 +51,11 @@ impl<'a, Actions: ModelTypes> FindFirst<'a, Actions> {
         self
     }
 
+    pub fn distinct(mut self, params: Vec<Actions::ScalarField>) -> Self {
+        self.distinct_params.extend(params);
+        self
+    }
+
     pub fn skip(mut self, skip: i64) -> Self {
         self.skip = Some(skip);
         self
@@ -54,15 +66,18 @@ impl<'a, Actions: ModelTypes> FindFirst<'a, Actions> {
         self
     }
 
-    fn to_selection(
+    /// Builds the query's selection, named after `Q`'s operation
+    /// so that the `*_or_throw` variant can reuse it.
+    pub(crate) fn to_selection<Q: ModelQuery<'a, Types = Actions>>(
         where_params: Vec<Actions::Where>,
         order_by_params: Vec<Actions::OrderBy>,
         cursor_params: Vec<Actions::Cursor>,
+        distinct_params: Vec<Actions::ScalarField>,
         skip: Option<i64>,
         take: Option<i64>,
         nested_selections: impl IntoIterator<Item = Selection>,
     ) -> Selection {
-        Self::base_selection(
+        Q::base_selection(
             [
                 (!where_params.is_empty()).then(|| {
                     (
@@ -99,6 +114,17 @@ impl<'a, Actions: ModelTypes> FindFirst<'a, Actions> {
                         ),
                     )
                 }),
+                (!distinct_params.is_empty()).then(|| {
+                    (
+                        "distinct".to_string(),
+                        PrismaValue::List(
+                            distinct_params
+                                .into_iter()
+                                .map(|field| PrismaValue::Enum(field.to_string()))
+                                .collect(),
+                        ),
+                    )
+                }),
                 skip.map(|skip| ("skip".to_string(), PrismaValue::Int(skip as i32).into())),
                 take.map(|take| ("take".to_string(), PrismaValue::Int(take as i32).into())),
             ]
@@ -114,15 +140,18 @@ impl<'a, Actions: ModelTypes> FindFirst<'a, Actions> {
     ) -> Select<'a, Option<S::Data>> {
         Select::new(
             self.client,
-            Operation::Read(Self::to_selection(
+            Operation::Read(Self::to_selection::<Self>(
                 self.where_params,
                 self.order_by_params,
                 self.cursor_params,
+                self.distinct_params,
                 self.skip,
                 self.take,
                 select.to_selections(),
             )),
         )
+        .with_timeout(self.timeout)
+        .for_model_query::<Self>()
     }
 
     pub fn include<I: IncludeType<ModelData = Actions::Data>>(
@@ -131,15 +160,18 @@ impl<'a, Actions: ModelTypes> FindFirst<'a, Actions> {
     ) -> Include<'a, Option<I::Data>> {
         Include::new(
             self.client,
-            Operation::Read(Self::to_selection(
+            Operation::Read(Self::to_selection::<Self>(
                 self.where_params,
                 self.order_by_params,
                 self.cursor_params,
+                self.distinct_params,
                 self.skip,
                 self.take,
                 include.to_selections(),
             )),
         )
+        .with_timeout(self.timeout)
+        .for_model_query::<Self>()
     }
 
     pub async fn exec(self) -> super::Result<Option<Actions::Data>> {
@@ -163,10 +195,11 @@ impl<'a, Actions: ModelTypes> Query<'a> for FindFirst<'a, Actions> {
         scalar_selections.extend(self.with_params.into_iter().map(Into::into));
 
         (
-            Operation::Read(Self::to_selection(
+            Operation::Read(Self::to_selection::<Self>(
                 self.where_params,
                 self.order_by_params,
                 self.cursor_params,
+                self.distinct_params,
                 self.skip,
                 self.take,
                 scalar_selections,
@@ -174,6 +207,16 @@ impl<'a, Actions: ModelTypes> Query<'a> for FindFirst<'a, Actions> {
             self.client,
         )
     }
+
+    fn context(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
+}
+
+impl<'a, Actions: ModelTypes> TimeoutQuery<'a> for FindFirst<'a, Actions> {
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for FindFirst<'a, Actions> {
@@ -213,3 +256,9 @@ impl<'a, Actions: ModelTypes> PaginatedQuery<'a> for FindFirst<'a, Actions> {
         self.take = Some(take);
     }
 }
+
+impl<'a, Actions: ModelTypes> DistinctQuery<'a> for FindFirst<'a, Actions> {
+    fn add_distinct(&mut self, param: Actions::ScalarField) {
+        self.distinct_params.push(param);
+    }
+}
@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use query_core::Operation;
+
+use crate::{
+    DistinctQuery, FindFirst, Include, IncludeType, ModelOperation, ModelQuery, ModelReadOperation,
+    ModelTypes, OrderByQuery, PaginatedQuery, PrismaClientInternals, Query, QueryConvert, Select,
+    SelectType, TimeoutQuery, WhereQuery, WithQuery,
+};
+
+/// A `FindFirst` that returns `QueryError::RecordNotFound` instead of `None`.
+pub struct FindFirstOrThrow<'a, Actions: ModelTypes>(FindFirst<'a, Actions>);
+
+impl<'a, Actions: ModelTypes> FindFirstOrThrow<'a, Actions> {
+    pub fn new(client: &'a PrismaClientInternals, where_params: Vec<Actions::Where>) -> Self {
+        Self(FindFirst::new(client, where_params))
+    }
+
+    pub fn with(self, param: impl Into<Actions::With>) -> Self {
+        Self(self.0.with(param))
+    }
+
+    pub fn order_by(self, param: Actions::OrderBy) -> Self {
+        Self(self.0.order_by(param))
+    }
+
+    pub fn cursor(self, param: Actions::Cursor) -> Self {
+        Self(self.0.cursor(param))
+    }
+
+    pub fn distinct(self, params: Vec<Actions::ScalarField>) -> Self {
+        Self(self.0.distinct(params))
+    }
+
+    pub fn skip(self, skip: i64) -> Self {
+        Self(self.0.skip(skip))
+    }
+
+    pub fn take(self, take: i64) -> Self {
+        Self(self.0.take(take))
+    }
+
+    pub fn select<S: SelectType<ModelData = Actions::Data>>(
+        self,
+        select: S,
+    ) -> Select<'a, S::Data> {
+        let query = self.0;
+
+        Select::new(
+            query.client,
+            Operation::Read(FindFirst::<Actions>::to_selection::<Self>(
+                query.where_params,
+                query.order_by_params,
+                query.cursor_params,
+                query.distinct_params,
+                query.skip,
+                query.take,
+                select.to_selections(),
+            )),
+        )
+        .with_timeout(query.timeout)
+        .for_model_query::<Self>()
+    }
+
+    pub fn include<I: IncludeType<ModelData = Actions::Data>>(
+        self,
+        include: I,
+    ) -> Include<'a, I::Data> {
+        let query = self.0;
+
+        Include::new(
+            query.client,
+            Operation::Read(FindFirst::<Actions>::to_selection::<Self>(
+                query.where_params,
+                query.order_by_params,
+                query.cursor_params,
+                query.distinct_params,
+                query.skip,
+                query.take,
+                include.to_selections(),
+            )),
+        )
+        .with_timeout(query.timeout)
+        .for_model_query::<Self>()
+    }
+
+    pub async fn exec(self) -> super::Result<Actions::Data> {
+        super::exec(self).await
+    }
+}
+
+impl<'a, Actions: ModelTypes> QueryConvert for FindFirstOrThrow<'a, Actions> {
+    type RawType = Actions::Data;
+    type ReturnValue = Self::RawType;
+
+    fn convert(raw: Self::RawType) -> super::Result<Self::ReturnValue> {
+        Ok(raw)
+    }
+}
+
+impl<'a, Actions: ModelTypes> Query<'a> for FindFirstOrThrow<'a, Actions> {
+    fn graphql(self) -> (Operation, &'a PrismaClientInternals) {
+        let query = self.0;
+
+        let mut scalar_selections = Actions::scalar_selections();
+
+        scalar_selections.extend(query.with_params.into_iter().map(Into::into));
+
+        (
+            Operation::Read(FindFirst::<Actions>::to_selection::<Self>(
+                query.where_params,
+                query.order_by_params,
+                query.cursor_params,
+                query.distinct_params,
+                query.skip,
+                query.take,
+                scalar_selections,
+            )),
+            query.client,
+        )
+    }
+
+    fn context(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
+}
+
+impl<'a, Actions: ModelTypes> TimeoutQuery<'a> for FindFirstOrThrow<'a, Actions> {
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        self.0.timeout_mut()
+    }
+}
+
+impl<'a, Actions: ModelTypes> ModelQuery<'a> for FindFirstOrThrow<'a, Actions> {
+    type Types = Actions;
+
+    const TYPE: ModelOperation = ModelOperation::Read(ModelReadOperation::FindFirstOrThrow);
+}
+
+impl<'a, Actions: ModelTypes> WhereQuery<'a> for FindFirstOrThrow<'a, Actions> {
+    fn add_where(&mut self, param: Actions::Where) {
+        self.0.add_where(param);
+    }
+}
+
+impl<'a, Actions: ModelTypes> WithQuery<'a> for FindFirstOrThrow<'a, Actions> {
+    fn add_with(&mut self, param: impl Into<Actions::With>) {
+        self.0.add_with(param);
+    }
+}
+
+impl<'a, Actions: ModelTypes> OrderByQuery<'a> for FindFirstOrThrow<'a, Actions> {
+    fn add_order_by(&mut self, param: Actions::OrderBy) {
+        self.0.add_order_by(param);
+    }
+}
+
+impl<'a, Actions: ModelTypes> PaginatedQuery<'a> for FindFirstOrThrow<'a, Actions> {
+    fn add_cursor(&mut self, param: Actions::Cursor) {
+        self.0.add_cursor(param);
+    }
+
+    fn set_skip(&mut self, skip: i64) {
+        self.0.set_skip(skip);
+    }
+
+    fn set_take(&mut self, take: i64) {
+        self.0.set_take(take);
+    }
+}
+
+impl<'a, Actions: ModelTypes> DistinctQuery<'a> for FindFirstOrThrow<'a, Actions> {
+    fn add_distinct(&mut self, param: Actions::ScalarField) {
+        self.0.add_distinct(param);
+    }
+}
@@ -1,17 +1,24 @@
+use std::time::Duration;
+
+use futures::{stream, Stream, StreamExt};
 use query_core::{ArgumentValue, Operation, Selection};
+use serde::{de::IgnoredAny, Deserialize};
 
 use crate::{
-    merge_fields, Include, IncludeType, ModelOperation, ModelQuery, ModelReadOperation, ModelTypes,
-    OrderByQuery, PaginatedQuery, PrismaClientInternals, PrismaValue, Query, QueryConvert, Select,
-    SelectType, WhereInput, WhereQuery, WithQuery,
+    merge_fields, BatchOptions, Count, CountAggregateResult, DistinctQuery, GroupBy, Include,
+    IncludeType, ModelOperation, ModelQuery, ModelReadOperation, ModelTypes, OrderByQuery, Page,
+    PaginatedQuery, PrimaryKeyTypes, PrismaClientInternals, PrismaValue, Query, QueryConvert,
+    QueryError, Select, SelectType, TimeoutQuery, WhereInput, WhereQuery, WithQuery,
 };
 
 pub struct FindMany<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
+    timeout: Option<Duration>,
     pub where_params: Vec<Actions::Where>,
     pub with_params: Vec<Actions::With>,
     pub order_by_params: Vec<Actions::OrderBy>,
     pub cursor_params: Vec<Actions::Cursor>,
+    pub distinct_params: Vec<Actions::ScalarField>,
     pub skip: Option<i64>,
     pub take: Option<i64>,
 }
@@ -20,10 +27,12 @@ impl<'a, Actions: ModelTypes> FindMany<'a, Actions> {
     pub fn new(client: &'a PrismaClientInternals, where_params: Vec<Actions::Where>) -> Self {
         Self {
             client,
+            timeout: None,
             where_params,
             with_params: vec![],
             order_by_params: vec![],
             cursor_params: vec![],
+            distinct_params: vec![],
             skip: None,
             take: None,
         }
@@ -44,6 +53,11 @@ impl<'a, Actions: ModelTypes> FindMany<'a, Actions> {
         self
     }
 
+    pub fn distinct(mut self, params: Vec<Actions::ScalarField>) -> Self {
+        self.distinct_params.extend(params);
+        self
+    }
+
     pub fn skip(mut self, skip: i64) -> Self {
         self.skip = Some(skip);
         self
@@ -54,59 +68,115 @@ impl<'a, Actions: ModelTypes> FindMany<'a, Actions> {
         self
     }
 
+    fn arguments(
+        where_params: Vec<Actions::Where>,
+        order_by_params: Vec<Actions::OrderBy>,
+        cursor_params: Vec<Actions::Cursor>,
+        distinct_params: Vec<Actions::ScalarField>,
+        skip: Option<i64>,
+        take: Option<i64>,
+    ) -> Vec<(String, PrismaValue)> {
+        [
+            (!where_params.is_empty()).then(|| {
+                (
+                    "where".to_string(),
+                    PrismaValue::Object(merge_fields(
+                        where_params
+                            .into_iter()
+                            .map(WhereInput::serialize)
+                            .map(|s| (s.field, s.value.into()))
+                            .collect(),
+                    ))
+                    .into(),
+                )
+            }),
+            (!order_by_params.is_empty()).then(|| {
+                (
+                    "orderBy".to_string(),
+                    PrismaValue::List(
+                        order_by_params
+                            .into_iter()
+                            .map(|p| PrismaValue::Object(vec![p.into()]))
+                            .collect(),
+                    )
+                    .into(),
+                )
+            }),
+            (!cursor_params.is_empty()).then(|| {
+                (
+                    "cursor".to_string(),
+                    PrismaValue::Object(
+                        cursor_params
+                            .into_iter()
+                            .map(WhereInput::serialize)
+                            .map(|s| (s.field, s.value.into()))
+                            .collect(),
+                    )
+                    .into(),
+                )
+            }),
+            (!distinct_params.is_empty()).then(|| {
+                (
+                    "distinct".to_string(),
+                    PrismaValue::List(
+                        distinct_params
+                            .into_iter()
+                            .map(|field| PrismaValue::Enum(field.to_string()))
+                            .collect(),
+                    ),
+                )
+            }),
+            skip.map(|skip| ("skip".to_string(), PrismaValue::Int(skip as i32).into())),
+            take.map(|take| ("take".to_string(), PrismaValue::Int(take as i32).into())),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// The `orderBy` argument for `order_by_params` followed by the primary key,
+    /// so that records are always returned in the same order when paginating.
+    fn order_by_with_primary_key(order_by_params: Vec<Actions::OrderBy>) -> (String, PrismaValue) {
+        let mut order_by = order_by_params
+            .into_iter()
+            .map(Into::into)
+            .collect::<Vec<(String, PrismaValue)>>();
+
+        for field in Actions::PRIMARY_KEY {
+            if !order_by.iter().any(|(name, _)| name == *field) {
+                order_by.push((field.to_string(), PrismaValue::String("asc".to_string())));
+            }
+        }
+
+        (
+            "orderBy".to_string(),
+            PrismaValue::List(
+                order_by
+                    .into_iter()
+                    .map(|param| PrismaValue::Object(vec![param]))
+                    .collect(),
+            ),
+        )
+    }
+
     fn to_selection(
         where_params: Vec<Actions::Where>,
         order_by_params: Vec<Actions::OrderBy>,
         cursor_params: Vec<Actions::Cursor>,
+        distinct_params: Vec<Actions::ScalarField>,
         skip: Option<i64>,
         take: Option<i64>,
         nested_selections: impl IntoIterator<Item = Selection>,
     ) -> Selection {
         Self::base_selection(
-            [
-                (!where_params.is_empty()).then(|| {
-                    (
-                        "where".to_string(),
-                        PrismaValue::Object(merge_fields(
-                            where_params
-                                .into_iter()
-                                .map(WhereInput::serialize)
-                                .map(|s| (s.field, s.value.into()))
-                                .collect(),
-                        ))
-                        .into(),
-                    )
-                }),
-                (!order_by_params.is_empty()).then(|| {
-                    (
-                        "orderBy".to_string(),
-                        PrismaValue::List(
-                            order_by_params
-                                .into_iter()
-                                .map(|p| PrismaValue::Object(vec![p.into()]))
-                                .collect(),
-                        )
-                        .into(),
-                    )
-                }),
-                (!cursor_params.is_empty()).then(|| {
-                    (
-                        "cursor".to_string(),
-                        PrismaValue::Object(
-                            cursor_params
-                                .into_iter()
-                                .map(WhereInput::serialize)
-                                .map(|s| (s.field, s.value.into()))
-                                .collect(),
-                        )
-                        .into(),
-                    )
-                }),
-                skip.map(|skip| ("skip".to_string(), PrismaValue::Int(skip as i32).into())),
-                take.map(|take| ("take".to_string(), PrismaValue::Int(take as i32).into())),
-            ]
-            .into_iter()
-            .flatten(),
+            Self::arguments(
+                where_params,
+                order_by_params,
+                cursor_params,
+                distinct_params,
+                skip,
+                take,
+            ),
             nested_selections,
         )
     }
@@ -121,11 +191,14 @@ impl<'a, Actions: ModelTypes> FindMany<'a, Actions> {
                 self.where_params,
                 self.order_by_params,
                 self.cursor_params,
+                self.distinct_params,
                 self.skip,
                 self.take,
                 select.to_selections(),
             )),
         )
+        .with_timeout(self.timeout)
+        .for_model_query::<Self>()
     }
 
     pub fn include<I: IncludeType<ModelData = Actions::Data>>(
@@ -138,11 +211,14 @@ impl<'a, Actions: ModelTypes> FindMany<'a, Actions> {
                 self.where_params,
                 self.order_by_params,
                 self.cursor_params,
+                self.distinct_params,
                 self.skip,
                 self.take,
                 include.to_selections(),
             )),
         )
+        .with_timeout(self.timeout)
+        .for_model_query::<Self>()
     }
 
     pub async fn exec(self) -> super::Result<Vec<Actions::Data>> {
@@ -150,6 +226,217 @@ impl<'a, Actions: ModelTypes> FindMany<'a, Actions> {
     }
 }
 
+impl<'a, Actions: PrimaryKeyTypes> FindMany<'a, Actions> {
+    /// Fetches matching records `page_size` at a time using cursor pagination
+    /// on the model's primary key, yielding them one at a time.
+    ///
+    /// `cursor`, `skip` and `take` apply to the stream as a whole,
+    /// and the primary key is appended to the ordering so that pages don't overlap.
+    pub fn stream(self, page_size: i64) -> impl Stream<Item = super::Result<Actions::Data>> + 'a
+    where
+        Actions: 'a,
+    {
+        let client = self.client;
+        let timeout = self.timeout;
+
+        let mut nested_selections = Actions::scalar_selections();
+        nested_selections.extend(self.with_params.into_iter().map(Into::into));
+
+        let mut arguments = Self::arguments(
+            self.where_params,
+            vec![],
+            vec![],
+            self.distinct_params,
+            None,
+            None,
+        );
+        arguments.push(Self::order_by_with_primary_key(self.order_by_params));
+
+        let cursor = (!self.cursor_params.is_empty()).then(|| {
+            PrismaValue::Object(
+                self.cursor_params
+                    .into_iter()
+                    .map(WhereInput::serialize)
+                    .map(|s| (s.field, s.value.into()))
+                    .collect(),
+            )
+        });
+
+        stream::unfold(Some((cursor, self.skip, self.take)), move |state| {
+            let arguments = arguments.clone();
+            let nested_selections = nested_selections.clone();
+
+            async move {
+                let (cursor, skip, remaining) = state?;
+
+                let take = remaining.map_or(page_size, |remaining| remaining.min(page_size));
+                if take <= 0 {
+                    return None;
+                }
+
+                let selection = Self::base_selection(
+                    arguments
+                        .into_iter()
+                        .chain(cursor.map(|cursor| ("cursor".to_string(), cursor)))
+                        .chain(skip.map(|skip| ("skip".to_string(), PrismaValue::Int(skip as i32))))
+                        .chain([("take".to_string(), PrismaValue::Int(take as i32))]),
+                    nested_selections,
+                );
+
+                let page =
+                    match Select::<Vec<Actions::Data>>::new(client, Operation::Read(selection))
+                        .with_timeout(timeout)
+                        .for_model_query::<Self>()
+                        .exec()
+                        .await
+                    {
+                        Ok(page) => page,
+                        Err(e) => return Some((vec![Err(e)], None)),
+                    };
+
+                // a full page means there may be more records after the last one
+                let next = page
+                    .last()
+                    .filter(|_| page.len() as i64 == take)
+                    .map(|last| {
+                        let cursor = WhereInput::serialize(Actions::primary_key_cursor(last));
+
+                        (
+                            Some(PrismaValue::Object(vec![cursor.into()])),
+                            Some(1),
+                            remaining.map(|remaining| remaining - take),
+                        )
+                    });
+
+                Some((page.into_iter().map(Ok).collect::<Vec<_>>(), next))
+            }
+        })
+        .flat_map(stream::iter)
+    }
+
+    /// Fetches page `page` of the query's results, starting from `1`,
+    /// along with the total number of records matching the query's filters and distinct fields.
+    ///
+    /// Overrides any `skip`, `take` or `cursor` set on the query.
+    /// Returns `QueryError::InvalidArgument` if `per_page` isn't positive.
+    ///
+    /// With `distinct`, the total is counted by fetching the distinct fields of every distinct record,
+    /// so it gets slower as the number of distinct records grows.
+    pub async fn paginate(
+        mut self,
+        page: i64,
+        per_page: i64,
+    ) -> super::Result<Page<Actions::Data, Actions::Cursor>> {
+        self.cursor_params = vec![];
+        self.skip = Some((page.max(1) - 1) * per_page);
+
+        self.exec_page(per_page).await
+    }
+
+    /// Fetches `per_page` records after `cursor`, or from the start if no cursor is provided,
+    /// along with the total number of records matching the query's filters and distinct fields.
+    /// The total includes records before `cursor`.
+    ///
+    /// A page's `next_cursor` can be passed to `paginate_after` to fetch the next page.
+    /// Overrides any `skip`, `take` or `cursor` set on the query.
+    /// Has the same `per_page` and `distinct` caveats as `paginate`.
+    pub async fn paginate_after(
+        mut self,
+        cursor: Option<Actions::Cursor>,
+        per_page: i64,
+    ) -> super::Result<Page<Actions::Data, Actions::Cursor>> {
+        self.skip = cursor.is_some().then_some(1);
+        self.cursor_params = cursor.into_iter().collect();
+
+        self.exec_page(per_page).await
+    }
+
+    async fn exec_page(self, per_page: i64) -> super::Result<Page<Actions::Data, Actions::Cursor>> {
+        if per_page <= 0 {
+            return Err(QueryError::InvalidArgument(format!(
+                "per_page must be greater than 0, got {per_page}"
+            )));
+        }
+
+        let client = self.client;
+
+        let total = if self.distinct_params.is_empty() {
+            let count = Count::<Actions>::new(client, self.where_params.clone());
+
+            Select::<PageTotal>::new(client, count.graphql().0).for_model_query::<Count<Actions>>()
+        } else {
+            // aggregate doesn't support distinct and the engine can't count groups,
+            // so each distinct record is fetched as a group of only its distinct fields
+            // and counted here instead
+            let groups = GroupBy::<Actions>::new(
+                client,
+                self.distinct_params.clone(),
+                self.where_params.clone(),
+            );
+
+            Select::<PageTotal>::new(client, groups.graphql().0)
+                .for_model_query::<GroupBy<Actions>>()
+        };
+
+        let mut nested_selections = Actions::scalar_selections();
+        nested_selections.extend(self.with_params.into_iter().map(Into::into));
+
+        // one extra record is fetched to determine if there are more after this page
+        let mut arguments = Self::arguments(
+            self.where_params,
+            vec![],
+            self.cursor_params,
+            self.distinct_params,
+            self.skip,
+            Some(per_page + 1),
+        );
+        arguments.push(Self::order_by_with_primary_key(self.order_by_params));
+
+        let find_many = Select::<Vec<Actions::Data>>::new(
+            client,
+            Operation::Read(Self::base_selection(arguments, nested_selections)),
+        )
+        .for_model_query::<Self>();
+
+        let options = BatchOptions {
+            timeout: self.timeout,
+            ..Default::default()
+        };
+
+        let (mut items, total) =
+            super::batch_with_options((find_many, total), client, options).await?;
+
+        let total = match total {
+            PageTotal::Count(count) => Count::<Actions>::convert(count)?,
+            PageTotal::Groups(groups) => groups.len() as i64,
+        };
+
+        let has_more = items.len() as i64 > per_page;
+        items.truncate(per_page as usize);
+
+        let next_cursor = items
+            .last()
+            .filter(|_| has_more)
+            .map(Actions::primary_key_cursor);
+
+        Ok(Page {
+            items,
+            total,
+            next_cursor,
+            has_more,
+        })
+    }
+}
+
+/// The total of a paginated query, either counted directly
+/// or as the groups of the query's distinct fields.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PageTotal {
+    Count(CountAggregateResult),
+    Groups(Vec<IgnoredAny>),
+}
+
 impl<'a, Actions: ModelTypes> QueryConvert for FindMany<'a, Actions> {
     type RawType = Vec<Actions::Data>;
     type ReturnValue = Self::RawType;
@@ -170,6 +457,7 @@ impl<'a, Actions: ModelTypes> Query<'a> for FindMany<'a, Actions> {
                 self.where_params,
                 self.order_by_params,
                 self.cursor_params,
+                self.distinct_params,
                 self.skip,
                 self.take,
                 scalar_selections,
@@ -177,6 +465,16 @@ impl<'a, Actions: ModelTypes> Query<'a> for FindMany<'a, Actions> {
             self.client,
         )
     }
+
+    fn context(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
+}
+
+impl<'a, Actions: ModelTypes> TimeoutQuery<'a> for FindMany<'a, Actions> {
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for FindMany<'a, Actions> {
@@ -223,6 +521,7 @@ pub struct ManyArgs<Actions: ModelTypes> {
     pub with_params: Vec<Actions::With>,
     pub order_by_params: Vec<Actions::OrderBy>,
     pub cursor_params: Vec<Actions::Cursor>,
+    pub distinct_params: Vec<Actions::ScalarField>,
     pub skip: Option<i64>,
     pub take: Option<i64>,
 }
@@ -234,6 +533,7 @@ impl<Actions: ModelTypes> ManyArgs<Actions> {
             with_params: vec![],
             order_by_params: vec![],
             cursor_params: vec![],
+            distinct_params: vec![],
             skip: None,
             take: None,
         }
@@ -254,6 +554,11 @@ impl<Actions: ModelTypes> ManyArgs<Actions> {
         self
     }
 
+    pub fn distinct(mut self, params: Vec<Actions::ScalarField>) -> Self {
+        self.distinct_params.extend(params);
+        self
+    }
+
     pub fn skip(mut self, skip: i64) -> Self {
         self.skip = Some(skip);
         self
@@ -303,6 +608,17 @@ impl<Actions: ModelTypes> ManyArgs<Actions> {
                     ),
                 )
             }),
+            (!self.distinct_params.is_empty()).then(|| {
+                (
+                    "distinct".to_string(),
+                    PrismaValue::List(
+                        self.distinct_params
+                            .into_iter()
+                            .map(|field| PrismaValue::Enum(field.to_string()))
+                            .collect(),
+                    ),
+                )
+            }),
             self.skip
                 .map(|skip| ("skip".to_string(), PrismaValue::Int(skip as i32))),
             self.take
@@ -320,3 +636,9 @@ impl<Actions: ModelTypes> ManyArgs<Actions> {
         (arguments, nested_selections)
     }
 }
+
+impl<'a, Actions: ModelTypes> DistinctQuery<'a> for FindMany<'a, Actions> {
+    fn add_distinct(&mut self, param: Actions::ScalarField) {
+        self.distinct_params.push(param);
+    }
+}
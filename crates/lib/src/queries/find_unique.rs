@@ -1,15 +1,16 @@
-use std::marker::PhantomData;
+use std::{marker::PhantomData, time::Duration};
 
 use query_core::{Operation, Selection};
 
 use crate::{
     Include, IncludeType, ModelOperation, ModelQuery, ModelReadOperation, ModelTypes,
-    PrismaClientInternals, PrismaValue, Query, QueryConvert, Select, SelectType, WhereInput,
-    WithQuery,
+    PrismaClientInternals, PrismaValue, Query, QueryConvert, Select, SelectType, TimeoutQuery,
+    WhereInput, WithQuery,
 };
 
 pub struct FindUnique<'a, Actions: ModelTypes> {
-    client: &'a PrismaClientInternals,
+    pub(crate) client: &'a PrismaClientInternals,
+    pub(crate) timeout: Option<Duration>,
     pub where_param: Actions::WhereUnique,
     pub with_params: Vec<Actions::With>,
     _data: PhantomData<(Actions::Set, Actions::Data)>,
@@ -19,6 +20,7 @@ impl<'a, Actions: ModelTypes> FindUnique<'a, Actions> {
     pub fn new(client: &'a PrismaClientInternals, where_param: Actions::WhereUnique) -> Self {
         Self {
             client,
+            timeout: None,
             where_param,
             with_params: vec![],
             _data: PhantomData,
@@ -30,11 +32,13 @@ impl<'a, Actions: ModelTypes> FindUnique<'a, Actions> {
         self
     }
 
-    fn to_selection(
+    /// Builds the query's selection, named after `Q`'s operation
+    /// so that the `*_or_throw` variant can reuse it.
+    pub(crate) fn to_selection<Q: ModelQuery<'a, Types = Actions>>(
         where_param: Actions::WhereUnique,
         nested_selections: impl IntoIterator<Item = Selection>,
     ) -> Selection {
-        Self::base_selection(
+        Q::base_selection(
             [(
                 "where".to_string(),
                 PrismaValue::Object(vec![where_param.serialize().transform_equals()]),
@@ -49,8 +53,13 @@ impl<'a, Actions: ModelTypes> FindUnique<'a, Actions> {
     ) -> Select<'a, Option<S::Data>> {
         Select::new(
             self.client,
-            Operation::Read(Self::to_selection(self.where_param, select.to_selections())),
+            Operation::Read(Self::to_selection::<Self>(
+                self.where_param,
+                select.to_selections(),
+            )),
         )
+        .with_timeout(self.timeout)
+        .for_model_query::<Self>()
     }
 
     pub fn include<I: IncludeType<ModelData = Actions::Data>>(
@@ -59,11 +68,13 @@ impl<'a, Actions: ModelTypes> FindUnique<'a, Actions> {
     ) -> Include<'a, Option<I::Data>> {
         Include::new(
             self.client,
-            Operation::Read(Self::to_selection(
+            Operation::Read(Self::to_selection::<Self>(
                 self.where_param,
                 include.to_selections(),
             )),
         )
+        .with_timeout(self.timeout)
+        .for_model_query::<Self>()
     }
 
     pub async fn exec(self) -> super::Result<Option<Actions::Data>> {
@@ -87,10 +98,23 @@ impl<'a, Actions: ModelTypes> Query<'a> for FindUnique<'a, Actions> {
         scalar_selections.extend(self.with_params.into_iter().map(Into::into));
 
         (
-            Operation::Read(Self::to_selection(self.where_param, scalar_selections)),
+            Operation::Read(Self::to_selection::<Self>(
+                self.where_param,
+                scalar_selections,
+            )),
             self.client,
         )
     }
+
+    fn context(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
+}
+
+impl<'a, Actions: ModelTypes> TimeoutQuery<'a> for FindUnique<'a, Actions> {
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for FindUnique<'a, Actions> {
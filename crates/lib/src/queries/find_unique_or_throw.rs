@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use query_core::Operation;
+
+use crate::{
+    FindUnique, Include, IncludeType, ModelOperation, ModelQuery, ModelReadOperation, ModelTypes,
+    PrismaClientInternals, Query, QueryConvert, Select, SelectType, TimeoutQuery, WithQuery,
+};
+
+/// A `FindUnique` that returns `QueryError::RecordNotFound` instead of `None`.
+pub struct FindUniqueOrThrow<'a, Actions: ModelTypes>(FindUnique<'a, Actions>);
+
+impl<'a, Actions: ModelTypes> FindUniqueOrThrow<'a, Actions> {
+    pub fn new(client: &'a PrismaClientInternals, where_param: Actions::WhereUnique) -> Self {
+        Self(FindUnique::new(client, where_param))
+    }
+
+    pub fn with(self, param: impl Into<Actions::With>) -> Self {
+        Self(self.0.with(param))
+    }
+
+    pub fn select<S: SelectType<ModelData = Actions::Data>>(
+        self,
+        select: S,
+    ) -> Select<'a, S::Data> {
+        Select::new(
+            self.0.client,
+            Operation::Read(FindUnique::<Actions>::to_selection::<Self>(
+                self.0.where_param,
+                select.to_selections(),
+            )),
+        )
+        .with_timeout(self.0.timeout)
+        .for_model_query::<Self>()
+    }
+
+    pub fn include<I: IncludeType<ModelData = Actions::Data>>(
+        self,
+        include: I,
+    ) -> Include<'a, I::Data> {
+        Include::new(
+            self.0.client,
+            Operation::Read(FindUnique::<Actions>::to_selection::<Self>(
+                self.0.where_param,
+                include.to_selections(),
+            )),
+        )
+        .with_timeout(self.0.timeout)
+        .for_model_query::<Self>()
+    }
+
+    pub async fn exec(self) -> super::Result<Actions::Data> {
+        super::exec(self).await
+    }
+}
+
+impl<'a, Actions: ModelTypes> QueryConvert for FindUniqueOrThrow<'a, Actions> {
+    type RawType = Actions::Data;
+    type ReturnValue = Self::RawType;
+
+    fn convert(raw: Self::RawType) -> super::Result<Self::ReturnValue> {
+        Ok(raw)
+    }
+}
+
+impl<'a, Actions: ModelTypes> Query<'a> for FindUniqueOrThrow<'a, Actions> {
+    fn graphql(self) -> (Operation, &'a PrismaClientInternals) {
+        let mut scalar_selections = Actions::scalar_selections();
+
+        scalar_selections.extend(self.0.with_params.into_iter().map(Into::into));
+
+        (
+            Operation::Read(FindUnique::<Actions>::to_selection::<Self>(
+                self.0.where_param,
+                scalar_selections,
+            )),
+            self.0.client,
+        )
+    }
+
+    fn context(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
+}
+
+impl<'a, Actions: ModelTypes> TimeoutQuery<'a> for FindUniqueOrThrow<'a, Actions> {
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        self.0.timeout_mut()
+    }
+}
+
+impl<'a, Actions: ModelTypes> ModelQuery<'a> for FindUniqueOrThrow<'a, Actions> {
+    type Types = Actions;
+
+    const TYPE: ModelOperation = ModelOperation::Read(ModelReadOperation::FindUniqueOrThrow);
+}
+
+impl<'a, Actions: ModelTypes> WithQuery<'a> for FindUniqueOrThrow<'a, Actions> {
+    fn add_with(&mut self, param: impl Into<Actions::With>) {
+        self.0.add_with(param);
+    }
+}
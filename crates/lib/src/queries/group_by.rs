@@ -0,0 +1,208 @@
+use std::time::Duration;
+
+use query_core::{Operation, Selection};
+
+use crate::{
+    merge_fields, sel, ModelOperation, ModelQuery, ModelReadOperation, ModelTypes,
+    PrismaClientInternals, PrismaValue, Query, QueryConvert, TimeoutQuery, WhereInput, WhereQuery,
+};
+
+use super::aggregate_selections;
+
+/// A filter applied to the result of an aggregate function inside a `having` clause.
+#[derive(Debug, Clone)]
+pub enum AggregateFilter<T> {
+    Equals(T),
+    Not(T),
+    InVec(Vec<T>),
+    NotInVec(Vec<T>),
+    Lt(T),
+    Lte(T),
+    Gt(T),
+    Gte(T),
+}
+
+impl<T> AggregateFilter<T> {
+    pub fn serialize(self, to_value: impl Fn(T) -> PrismaValue) -> (String, PrismaValue) {
+        let (name, value) = match self {
+            Self::Equals(value) => ("equals", to_value(value)),
+            Self::Not(value) => ("not", to_value(value)),
+            Self::InVec(values) => (
+                "in",
+                PrismaValue::List(values.into_iter().map(&to_value).collect()),
+            ),
+            Self::NotInVec(values) => (
+                "notIn",
+                PrismaValue::List(values.into_iter().map(&to_value).collect()),
+            ),
+            Self::Lt(value) => ("lt", to_value(value)),
+            Self::Lte(value) => ("lte", to_value(value)),
+            Self::Gt(value) => ("gt", to_value(value)),
+            Self::Gte(value) => ("gte", to_value(value)),
+        };
+
+        (name.to_string(), value)
+    }
+}
+
+pub struct GroupBy<'a, Actions: ModelTypes> {
+    client: &'a PrismaClientInternals,
+    timeout: Option<Duration>,
+    pub by: Vec<Actions::ScalarField>,
+    pub where_params: Vec<Actions::Where>,
+    pub having_params: Vec<Actions::Having>,
+    pub order_by_params: Vec<Actions::OrderByWithAggregation>,
+    pub skip: Option<i64>,
+    pub take: Option<i64>,
+    pub aggregate_params: Vec<Actions::Aggregate>,
+}
+
+impl<'a, Actions: ModelTypes> GroupBy<'a, Actions> {
+    pub fn new(
+        client: &'a PrismaClientInternals,
+        by: Vec<Actions::ScalarField>,
+        where_params: Vec<Actions::Where>,
+    ) -> Self {
+        Self {
+            client,
+            timeout: None,
+            by,
+            where_params,
+            having_params: vec![],
+            order_by_params: vec![],
+            skip: None,
+            take: None,
+            aggregate_params: vec![],
+        }
+    }
+
+    pub fn select(mut self, params: Vec<Actions::Aggregate>) -> Self {
+        self.aggregate_params.extend(params);
+        self
+    }
+
+    pub fn having(mut self, params: Vec<Actions::Having>) -> Self {
+        self.having_params.extend(params);
+        self
+    }
+
+    pub fn order_by(mut self, param: Actions::OrderByWithAggregation) -> Self {
+        self.order_by_params.push(param);
+        self
+    }
+
+    pub fn skip(mut self, skip: i64) -> Self {
+        self.skip = Some(skip);
+        self
+    }
+
+    pub fn take(mut self, take: i64) -> Self {
+        self.take = Some(take);
+        self
+    }
+
+    pub async fn exec(self) -> super::Result<Vec<Actions::GroupByResult>> {
+        super::exec(self).await
+    }
+}
+
+impl<'a, Actions: ModelTypes> QueryConvert for GroupBy<'a, Actions> {
+    type RawType = Vec<Actions::GroupByResult>;
+    type ReturnValue = Self::RawType;
+
+    fn convert(raw: Self::RawType) -> super::Result<Self::ReturnValue> {
+        Ok(raw)
+    }
+}
+
+impl<'a, Actions: ModelTypes> Query<'a> for GroupBy<'a, Actions> {
+    fn graphql(self) -> (Operation, &'a PrismaClientInternals) {
+        let by = self
+            .by
+            .into_iter()
+            .map(|field| field.to_string())
+            .collect::<Vec<_>>();
+
+        let nested_selections = by
+            .iter()
+            .map(|field| sel(field))
+            .chain(aggregate_selections(self.aggregate_params))
+            .collect::<Vec<Selection>>();
+
+        (
+            Operation::Read(Self::base_selection(
+                [
+                    Some((
+                        "by".to_string(),
+                        PrismaValue::List(by.into_iter().map(PrismaValue::Enum).collect()),
+                    )),
+                    (!self.where_params.is_empty()).then(|| {
+                        (
+                            "where".to_string(),
+                            PrismaValue::Object(merge_fields(
+                                self.where_params
+                                    .into_iter()
+                                    .map(WhereInput::serialize)
+                                    .map(|s| (s.field, s.value.into()))
+                                    .collect(),
+                            )),
+                        )
+                    }),
+                    (!self.having_params.is_empty()).then(|| {
+                        (
+                            "having".to_string(),
+                            PrismaValue::Object(merge_fields(
+                                self.having_params
+                                    .into_iter()
+                                    .map(WhereInput::serialize)
+                                    .map(|s| (s.field, s.value.into()))
+                                    .collect(),
+                            )),
+                        )
+                    }),
+                    (!self.order_by_params.is_empty()).then(|| {
+                        (
+                            "orderBy".to_string(),
+                            PrismaValue::List(
+                                self.order_by_params
+                                    .into_iter()
+                                    .map(|p| PrismaValue::Object(vec![p.into()]))
+                                    .collect(),
+                            ),
+                        )
+                    }),
+                    self.skip
+                        .map(|skip| ("skip".to_string(), PrismaValue::Int(skip as i32))),
+                    self.take
+                        .map(|take| ("take".to_string(), PrismaValue::Int(take as i32))),
+                ]
+                .into_iter()
+                .flatten(),
+                nested_selections,
+            )),
+            self.client,
+        )
+    }
+
+    fn context(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
+}
+
+impl<'a, Actions: ModelTypes> TimeoutQuery<'a> for GroupBy<'a, Actions> {
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
+}
+
+impl<'a, Actions: ModelTypes> ModelQuery<'a> for GroupBy<'a, Actions> {
+    type Types = Actions;
+
+    const TYPE: ModelOperation = ModelOperation::Read(ModelReadOperation::GroupBy);
+}
+
+impl<'a, Actions: ModelTypes> WhereQuery<'a> for GroupBy<'a, Actions> {
+    fn add_where(&mut self, param: Actions::Where) {
+        self.where_params.push(param);
+    }
+}
@@ -1,7 +1,7 @@
 use query_core::{Operation, Selection};
-use std::marker::PhantomData;
+use std::{marker::PhantomData, time::Duration};
 
-use crate::{PrismaClientInternals, Query, QueryConvert};
+use crate::{ModelOperation, ModelQuery, PrismaClientInternals, Query, QueryConvert, TimeoutQuery};
 
 use super::query;
 
@@ -16,6 +16,8 @@ pub trait IncludeType {
 pub struct Include<'a, Data> {
     operation: Operation,
     client: &'a PrismaClientInternals,
+    timeout: Option<Duration>,
+    context: Option<(&'static str, ModelOperation)>,
     _data: PhantomData<Data>,
 }
 
@@ -23,11 +25,25 @@ impl<'a, Data: query::Data> Include<'a, Data> {
     pub fn new(client: &'a PrismaClientInternals, operation: Operation) -> Self {
         Self {
             client,
+            timeout: None,
+            context: None,
             operation,
             _data: PhantomData {},
         }
     }
 
+    /// Carries over the timeout of the query being converted into a selection.
+    pub(crate) fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Reports the operation as the model and action of `Q`.
+    pub(crate) fn for_model_query<Q: ModelQuery<'a>>(mut self) -> Self {
+        self.context = Some((Q::Types::MODEL, Q::TYPE));
+        self
+    }
+
     pub async fn exec(self) -> super::Result<Data> {
         super::exec(self).await
     }
@@ -46,4 +62,14 @@ impl<'a, Data: query::Data> Query<'a> for Include<'a, Data> {
     fn graphql(self) -> (Operation, &'a PrismaClientInternals) {
         (self.operation, self.client)
     }
+
+    fn context(&self) -> Option<(&'static str, ModelOperation)> {
+        self.context
+    }
+}
+
+impl<'a, Data: query::Data> TimeoutQuery<'a> for Include<'a, Data> {
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
 }
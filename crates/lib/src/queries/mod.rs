@@ -1,3 +1,4 @@
+mod aggregate;
 mod batch;
 mod count;
 mod create;
@@ -7,11 +8,16 @@ mod delete;
 mod delete_many;
 mod error;
 mod execute_raw;
+mod exists;
 mod find_first;
+mod find_first_or_throw;
 mod find_many;
 mod find_unique;
+mod find_unique_or_throw;
+mod group_by;
 mod include;
 mod mongo_raw;
+mod page;
 mod query;
 mod query_raw;
 mod select;
@@ -20,6 +26,7 @@ mod update_many;
 mod update_unchecked;
 mod upsert;
 
+pub use aggregate::*;
 pub use batch::*;
 pub use count::*;
 pub use create::*;
@@ -29,11 +36,16 @@ pub use delete::*;
 pub use delete_many::*;
 pub use error::*;
 pub use execute_raw::*;
+pub use exists::*;
 pub use find_first::*;
+pub use find_first_or_throw::*;
 pub use find_many::*;
 pub use find_unique::*;
+pub use find_unique_or_throw::*;
+pub use group_by::*;
 pub use include::*;
 pub use mongo_raw::*;
+pub use page::*;
 pub use query::*;
 pub use query_raw::*;
 pub use select::*;
@@ -48,7 +60,7 @@ use serde::de::IntoDeserializer;
 use serde::Deserialize;
 use std::future::Future;
 
-use crate::{ExecutionEngine, PrismaValue};
+use crate::{ExecutionEngine, PrismaValue, QueryContext};
 
 pub enum SerializedWhereValue {
     Object(Vec<(String, PrismaValue)>),
@@ -110,12 +122,15 @@ impl Into<(String, PrismaValue)> for SerializedWhereInput {
     }
 }
 
-pub fn exec<'a, Q: Query<'a> + 'a>(
-    query: Q,
+pub fn exec<'a, Q: TimeoutQuery<'a> + 'a>(
+    mut query: Q,
 ) -> impl Future<Output = Result<<Q as QueryConvert>::ReturnValue>> + 'a {
+    let timeout = *query.timeout_mut();
+    let context = query.context();
     let (op, client) = query.graphql();
+    let ctx = QueryContext::new(op, context);
 
-    client.execute(op).map(|value| {
+    client.execute(ctx, timeout).map(|value| {
         let value = value?;
 
         Ok(match client.engine {
@@ -1,17 +1,18 @@
-use std::marker::PhantomData;
+use std::{marker::PhantomData, time::Duration};
 
 use prisma_models::PrismaValue;
 use query_core::{Operation, Selection};
 use serde::de::DeserializeOwned;
 use serde_json::Value;
 
-use crate::{ModelTypes, PrismaClientInternals, Query, QueryConvert, QueryError};
+use crate::{ModelTypes, PrismaClientInternals, Query, QueryConvert, QueryError, TimeoutQuery};
 
 pub struct RunCommandRaw<'a, Data>
 where
     Data: DeserializeOwned,
 {
     client: &'a PrismaClientInternals,
+    timeout: Option<Duration>,
     command: Value,
     _data: PhantomData<Data>,
 }
@@ -22,6 +23,7 @@ where
     pub fn new(client: &'a PrismaClientInternals, command: Value) -> Self {
         Self {
             client,
+            timeout: None,
             command,
             _data: PhantomData,
         }
@@ -70,8 +72,18 @@ where
     }
 }
 
+impl<'a, Data> TimeoutQuery<'a> for RunCommandRaw<'a, Data>
+where
+    Data: DeserializeOwned + 'static,
+{
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
+}
+
 pub struct FindRaw<'a, Types, Data> {
     client: &'a PrismaClientInternals,
+    timeout: Option<Duration>,
     filter: Option<Value>,
     options: Option<Value>,
     _data: PhantomData<(Data, Types)>,
@@ -84,6 +96,7 @@ where
     pub fn new(client: &'a PrismaClientInternals) -> Self {
         Self {
             client,
+            timeout: None,
             filter: None,
             options: None,
             _data: PhantomData,
@@ -157,8 +170,19 @@ where
     }
 }
 
+impl<'a, Types, Data> TimeoutQuery<'a> for FindRaw<'a, Types, Data>
+where
+    Types: ModelTypes,
+    Data: DeserializeOwned + 'static,
+{
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
+}
+
 pub struct AggregateRaw<'a, Types, Data> {
     client: &'a PrismaClientInternals,
+    timeout: Option<Duration>,
     pipeline: Option<Value>,
     options: Option<Value>,
     _data: PhantomData<(Data, Types)>,
@@ -171,6 +195,7 @@ where
     pub fn new(client: &'a PrismaClientInternals) -> Self {
         Self {
             client,
+            timeout: None,
             pipeline: None,
             options: None,
             _data: PhantomData,
@@ -243,3 +268,13 @@ where
         )
     }
 }
+
+impl<'a, Types, Data> TimeoutQuery<'a> for AggregateRaw<'a, Types, Data>
+where
+    Types: ModelTypes,
+    Data: DeserializeOwned + 'static,
+{
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
+}
@@ -0,0 +1,10 @@
+/// A page of records returned by `paginate` and `paginate_after`.
+#[derive(Debug, Clone)]
+pub struct Page<Data, Cursor> {
+    pub items: Vec<Data>,
+    /// The number of records matching the query's filters across all pages.
+    pub total: i64,
+    /// A cursor pointing at the last item, if there are more records after this page.
+    pub next_cursor: Option<Cursor>,
+    pub has_more: bool,
+}
@@ -1,7 +1,9 @@
+use std::time::Duration;
+
 use query_core::{Operation, Selection};
 use serde::de::DeserializeOwned;
 
-use crate::{PrismaClientInternals, PrismaValue, WhereInput};
+use crate::{AggregateFunction, PrismaClientInternals, PrismaValue, WhereInput};
 
 pub trait QueryConvert {
     type RawType: Data;
@@ -14,38 +16,81 @@ pub trait QueryConvert {
 
 pub trait Query<'a>: QueryConvert {
     fn graphql(self) -> (Operation, &'a PrismaClientInternals);
+
+    /// The model and action of the query, which are passed to middleware
+    /// and attached to its errors. `None` for raw queries.
+    fn context(&self) -> Option<(&'static str, ModelOperation)> {
+        None
+    }
+}
+
+/// A query that can be given its own timeout.
+pub trait TimeoutQuery<'a>: Query<'a> + Sized {
+    /// The query's own timeout, `None` if it uses the client's default query timeout.
+    fn timeout_mut(&mut self) -> &mut Option<Duration>;
+
+    /// Cancels the query if it takes longer than `timeout`,
+    /// overriding the client's default query timeout.
+    fn timeout(mut self, timeout: Duration) -> Self {
+        *self.timeout_mut() = Some(timeout);
+        self
+    }
 }
 
 pub trait ModelTypes {
     type Data: Data;
-    type Where: WhereInput;
+    type Where: WhereInput + Clone;
     type WhereUnique: WhereInput;
     type UncheckedSet: Into<(String, PrismaValue)>;
     type Set: Into<(String, PrismaValue)>;
     type With: Into<Selection>;
     type OrderBy: Into<(String, PrismaValue)>;
     type Cursor: WhereInput;
+    type Aggregate: Into<(AggregateFunction, String)>;
+    type AggregateResult: Data;
+    type ScalarField: ToString + Clone;
+    type Having: WhereInput;
+    type OrderByWithAggregation: Into<(String, PrismaValue)>;
+    type GroupByResult: Data;
 
     const MODEL: &'static str;
+    /// The fields that uniquely identify a record, either the primary key
+    /// or the first unique constraint on required fields if the model has no primary key.
+    const PRIMARY_KEY: &'static [&'static str];
 
     fn scalar_selections() -> Vec<Selection>;
 }
 
+/// Implemented for models with a primary key or a unique constraint on required fields,
+/// which cursor based pagination relies on to know where the next page starts.
+pub trait PrimaryKeyTypes: ModelTypes {
+    /// Creates a cursor pointing at a record using its `PRIMARY_KEY` fields.
+    fn primary_key_cursor(data: &Self::Data) -> Self::Cursor;
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ModelReadOperation {
     FindUnique,
+    FindUniqueOrThrow,
     FindFirst,
+    FindFirstOrThrow,
     FindMany,
     Count,
+    Aggregate,
+    GroupBy,
 }
 
 impl ModelReadOperation {
     pub fn name(&self) -> &'static str {
         match self {
             Self::FindUnique => "findUnique",
+            Self::FindUniqueOrThrow => "findUniqueOrThrow",
             Self::FindFirst => "findFirst",
+            Self::FindFirstOrThrow => "findFirstOrThrow",
             Self::FindMany => "findMany",
             Self::Count => "aggregate",
+            Self::Aggregate => "aggregate",
+            Self::GroupBy => "groupBy",
         }
     }
 }
@@ -129,6 +174,10 @@ pub trait PaginatedQuery<'a>: ModelQuery<'a> {
     fn set_take(&mut self, take: i64);
 }
 
+pub trait DistinctQuery<'a>: ModelQuery<'a> {
+    fn add_distinct(&mut self, param: <<Self as ModelQuery<'a>>::Types as ModelTypes>::ScalarField);
+}
+
 pub trait UncheckedSetQuery<'a>: ModelQuery<'a> {
     fn add_unchecked_set(
         &mut self,
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, marker::PhantomData};
+use std::{collections::HashMap, marker::PhantomData, time::Duration};
 
 use prisma_models::PrismaValue;
 use query_core::{Operation, Selection};
@@ -7,7 +7,7 @@ use serde_json::Value;
 
 use crate::{
     raw::{Raw, RawOperationData, RawPrismaValue},
-    PrismaClientInternals, Query, QueryConvert, QueryError,
+    PrismaClientInternals, Query, QueryConvert, QueryError, TimeoutQuery,
 };
 
 pub struct QueryRaw<'a, Data>
@@ -15,6 +15,7 @@ where
     Data: DeserializeOwned,
 {
     client: &'a PrismaClientInternals,
+    timeout: Option<Duration>,
     sql: String,
     params: Vec<Value>,
     _data: PhantomData<Data>,
@@ -29,6 +30,7 @@ where
 
         Self {
             client,
+            timeout: None,
             sql,
             params,
             _data: PhantomData,
@@ -99,3 +101,12 @@ where
         )
     }
 }
+
+impl<'a, Data> TimeoutQuery<'a> for QueryRaw<'a, Data>
+where
+    Data: DeserializeOwned + 'static,
+{
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
+}
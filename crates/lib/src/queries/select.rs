@@ -1,9 +1,11 @@
-use std::marker::PhantomData;
+use std::{marker::PhantomData, time::Duration};
 
 use query_core::{Operation, Selection};
 use serde::de::DeserializeOwned;
 
-use crate::{Data, PrismaClientInternals, Query, QueryConvert};
+use crate::{
+    Data, ModelOperation, ModelQuery, PrismaClientInternals, Query, QueryConvert, TimeoutQuery,
+};
 
 pub trait SelectType {
     // TODO: ModelActions
@@ -16,6 +18,8 @@ pub trait SelectType {
 pub struct Select<'a, Data: DeserializeOwned> {
     operation: Operation,
     client: &'a PrismaClientInternals,
+    timeout: Option<Duration>,
+    context: Option<(&'static str, ModelOperation)>,
     _data: PhantomData<Data>,
 }
 
@@ -23,11 +27,25 @@ impl<'a, Data: crate::Data> Select<'a, Data> {
     pub fn new(client: &'a PrismaClientInternals, operation: Operation) -> Self {
         Self {
             client,
+            timeout: None,
+            context: None,
             operation,
             _data: PhantomData {},
         }
     }
 
+    /// Carries over the timeout of the query being converted into a selection.
+    pub(crate) fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Reports the operation as the model and action of `Q`.
+    pub(crate) fn for_model_query<Q: ModelQuery<'a>>(mut self) -> Self {
+        self.context = Some((Q::Types::MODEL, Q::TYPE));
+        self
+    }
+
     pub async fn exec(self) -> super::Result<Data> {
         super::exec(self).await
     }
@@ -46,4 +64,14 @@ impl<'a, Data: DeserializeOwned + 'static> Query<'a> for Select<'a, Data> {
     fn graphql(self) -> (Operation, &'a PrismaClientInternals) {
         (self.operation, self.client)
     }
+
+    fn context(&self) -> Option<(&'static str, ModelOperation)> {
+        self.context
+    }
+}
+
+impl<'a, Data: DeserializeOwned + 'static> TimeoutQuery<'a> for Select<'a, Data> {
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
 }
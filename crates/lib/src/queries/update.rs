@@ -1,13 +1,16 @@
+use std::time::Duration;
+
 use query_core::{Operation, Selection};
 
 use crate::{
     merge_fields, Include, IncludeType, ModelOperation, ModelQuery, ModelTypes,
     ModelWriteOperation, PrismaClientInternals, PrismaValue, Query, QueryConvert, Select,
-    SelectType, SetQuery, WhereInput, WithQuery,
+    SelectType, SetQuery, TimeoutQuery, WhereInput, WithQuery,
 };
 
 pub struct Update<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
+    timeout: Option<Duration>,
     pub where_param: Actions::WhereUnique,
     pub set_params: Vec<Actions::Set>,
     pub with_params: Vec<Actions::With>,
@@ -22,6 +25,7 @@ impl<'a, Actions: ModelTypes> Update<'a, Actions> {
     ) -> Self {
         Self {
             client,
+            timeout: None,
             where_param,
             set_params,
             with_params,
@@ -67,6 +71,8 @@ impl<'a, Actions: ModelTypes> Update<'a, Actions> {
                 select.to_selections(),
             )),
         )
+        .with_timeout(self.timeout)
+        .for_model_query::<Self>()
     }
 
     pub fn include<I: IncludeType<ModelData = Actions::Data>>(
@@ -81,6 +87,8 @@ impl<'a, Actions: ModelTypes> Update<'a, Actions> {
                 include.to_selections(),
             )),
         )
+        .with_timeout(self.timeout)
+        .for_model_query::<Self>()
     }
 
     pub async fn exec(self) -> super::Result<Actions::Data> {
@@ -112,6 +120,16 @@ impl<'a, Actions: ModelTypes> Query<'a> for Update<'a, Actions> {
             self.client,
         )
     }
+
+    fn context(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
+}
+
+impl<'a, Actions: ModelTypes> TimeoutQuery<'a> for Update<'a, Actions> {
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for Update<'a, Actions> {
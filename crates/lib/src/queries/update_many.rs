@@ -1,12 +1,16 @@
+use std::time::Duration;
+
 use query_core::Operation;
 
 use crate::{
     merge_fields, BatchResult, ModelOperation, ModelQuery, ModelTypes, ModelWriteOperation,
-    PrismaClientInternals, PrismaValue, Query, QueryConvert, SetQuery, WhereInput, WhereQuery,
+    PrismaClientInternals, PrismaValue, Query, QueryConvert, SetQuery, TimeoutQuery, WhereInput,
+    WhereQuery,
 };
 
 pub struct UpdateMany<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
+    timeout: Option<Duration>,
     pub where_params: Vec<Actions::Where>,
     pub set_params: Vec<Actions::Set>,
 }
@@ -19,6 +23,7 @@ impl<'a, Actions: ModelTypes> UpdateMany<'a, Actions> {
     ) -> Self {
         Self {
             client,
+            timeout: None,
             where_params,
             set_params,
         }
@@ -73,6 +78,16 @@ impl<'a, Actions: ModelTypes> Query<'a> for UpdateMany<'a, Actions> {
             self.client,
         )
     }
+
+    fn context(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
+}
+
+impl<'a, Actions: ModelTypes> TimeoutQuery<'a> for UpdateMany<'a, Actions> {
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for UpdateMany<'a, Actions> {
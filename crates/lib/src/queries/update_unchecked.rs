@@ -1,14 +1,17 @@
+use std::time::Duration;
+
 use crate::PrismaValue;
 use query_core::{Operation, Selection};
 
 use crate::{
     merge_fields, Include, IncludeType, ModelOperation, ModelQuery, ModelTypes,
     ModelWriteOperation, PrismaClientInternals, Query, QueryConvert, Select, SelectType,
-    UncheckedSetQuery, WhereInput, WithQuery,
+    TimeoutQuery, UncheckedSetQuery, WhereInput, WithQuery,
 };
 
 pub struct UpdateUnchecked<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
+    timeout: Option<Duration>,
     pub where_param: Actions::WhereUnique,
     pub set_params: Vec<Actions::UncheckedSet>,
     pub with_params: Vec<Actions::With>,
@@ -23,6 +26,7 @@ impl<'a, Actions: ModelTypes> UpdateUnchecked<'a, Actions> {
     ) -> Self {
         Self {
             client,
+            timeout: None,
             where_param,
             set_params,
             with_params,
@@ -68,6 +72,8 @@ impl<'a, Actions: ModelTypes> UpdateUnchecked<'a, Actions> {
                 select.to_selections(),
             )),
         )
+        .with_timeout(self.timeout)
+        .for_model_query::<Self>()
     }
 
     pub fn include<I: IncludeType<ModelData = Actions::Data>>(
@@ -82,6 +88,8 @@ impl<'a, Actions: ModelTypes> UpdateUnchecked<'a, Actions> {
                 include.to_selections(),
             )),
         )
+        .with_timeout(self.timeout)
+        .for_model_query::<Self>()
     }
 
     pub async fn exec(self) -> super::Result<Actions::Data> {
@@ -113,6 +121,16 @@ impl<'a, Actions: ModelTypes> Query<'a> for UpdateUnchecked<'a, Actions> {
             self.client,
         )
     }
+
+    fn context(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
+}
+
+impl<'a, Actions: ModelTypes> TimeoutQuery<'a> for UpdateUnchecked<'a, Actions> {
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for UpdateUnchecked<'a, Actions> {
@@ -1,13 +1,16 @@
+use std::time::Duration;
+
 use query_core::{Operation, Selection};
 
 use crate::{
     Include, IncludeType, ModelOperation, ModelQuery, ModelTypes, ModelWriteOperation,
-    PrismaClientInternals, PrismaValue, Query, QueryConvert, Select, SelectType, WhereInput,
-    WithQuery,
+    PrismaClientInternals, PrismaValue, Query, QueryConvert, Select, SelectType, TimeoutQuery,
+    WhereInput, WithQuery,
 };
 
 pub struct Upsert<'a, Actions: ModelTypes> {
     client: &'a PrismaClientInternals,
+    timeout: Option<Duration>,
     pub where_param: Actions::WhereUnique,
     pub create_params: Vec<Actions::Set>,
     pub update_params: Vec<Actions::Set>,
@@ -23,6 +26,7 @@ impl<'a, Actions: ModelTypes> Upsert<'a, Actions> {
     ) -> Self {
         Self {
             client,
+            timeout: None,
             where_param,
             create_params,
             update_params,
@@ -73,6 +77,8 @@ impl<'a, Actions: ModelTypes> Upsert<'a, Actions> {
                 select.to_selections(),
             )),
         )
+        .with_timeout(self.timeout)
+        .for_model_query::<Self>()
     }
 
     pub fn include<I: IncludeType<ModelData = Actions::Data>>(
@@ -88,6 +94,8 @@ impl<'a, Actions: ModelTypes> Upsert<'a, Actions> {
                 select.to_selections(),
             )),
         )
+        .with_timeout(self.timeout)
+        .for_model_query::<Self>()
     }
 
     pub async fn exec(self) -> super::Result<Actions::Data> {
@@ -120,6 +128,16 @@ impl<'a, Actions: ModelTypes> Query<'a> for Upsert<'a, Actions> {
             self.client,
         )
     }
+
+    fn context(&self) -> Option<(&'static str, ModelOperation)> {
+        Some((Actions::MODEL, Self::TYPE))
+    }
+}
+
+impl<'a, Actions: ModelTypes> TimeoutQuery<'a> for Upsert<'a, Actions> {
+    fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
 }
 
 impl<'a, Actions: ModelTypes> ModelQuery<'a> for Upsert<'a, Actions> {
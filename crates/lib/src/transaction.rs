@@ -1,14 +1,24 @@
-use std::{future::Future, marker::PhantomData};
+use std::{
+    future::Future,
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
-use query_core::{protocol::EngineProtocol, TransactionOptions, TxId};
+use query_core::{TransactionOptions, TxId};
 
-use crate::{ExecutionEngine, PrismaClient, PrismaClientInternals, QueryError};
+use crate::{
+    connection::ConnectionState, metrics::MetricsRecorder, ErrorKind, ExecuteRaw, ExecutionEngine,
+    ExecutorConnector, PrismaClient, PrismaClientInternals, Query, QueryError, Raw,
+};
 
 pub struct TransactionBuilder<'a, TClient> {
     client: &'a TClient,
     internals: &'a PrismaClientInternals,
-    timeout: u64,
-    max_wait: u64,
+    // unset options use the engine's defaults,
+    // and setting any of them on a nested transaction is an error
+    timeout: Option<u64>,
+    max_wait: Option<u64>,
     isolation_level: Option<String>,
 }
 
@@ -17,20 +27,31 @@ impl<'a, TClient: PrismaClient> TransactionBuilder<'a, TClient> {
         Self {
             client,
             internals,
-            timeout: 5000,
-            max_wait: 2000,
+            timeout: None,
+            max_wait: None,
             isolation_level: None,
         }
     }
 
+    /// How long the transaction can run for in milliseconds, 5000 by default.
+    /// Can't be set on a nested transaction, which is bound by the outer transaction's timeout.
     pub fn with_timeout(self, timeout: u64) -> Self {
-        Self { timeout, ..self }
+        Self {
+            timeout: Some(timeout),
+            ..self
+        }
     }
 
+    /// How long to wait for a connection to start the transaction on in milliseconds, 2000 by default.
+    /// Can't be set on a nested transaction, which uses the outer transaction's connection.
     pub fn with_max_wait(self, max_wait: u64) -> Self {
-        Self { max_wait, ..self }
+        Self {
+            max_wait: Some(max_wait),
+            ..self
+        }
     }
 
+    /// Can't be set on a nested transaction, which uses the outer transaction's isolation level.
     pub fn with_isolation_level(self, isolation_level: impl TransactionIsolationLevel) -> Self {
         Self {
             isolation_level: Some(isolation_level.to_string()),
@@ -38,6 +59,22 @@ impl<'a, TClient: PrismaClient> TransactionBuilder<'a, TClient> {
         }
     }
 
+    /// Re-runs the transaction up to `retries` times if it fails with a retryable error,
+    /// waiting according to `backoff` between attempts.
+    /// By default, write conflicts, deadlocks and connection failures are retried.
+    pub fn with_retries(
+        self,
+        retries: u32,
+        backoff: Backoff,
+    ) -> RetryingTransactionBuilder<'a, TClient, RetryableErrors> {
+        RetryingTransactionBuilder {
+            builder: self,
+            retries,
+            backoff,
+            condition: RetryableErrors,
+        }
+    }
+
     pub async fn run<TErr, TRet, TFut, TFn>(self, tx: TFn) -> Result<TRet, TErr>
     where
         TFut: Future<Output = Result<TRet, TErr>>,
@@ -45,29 +82,114 @@ impl<'a, TClient: PrismaClient> TransactionBuilder<'a, TClient> {
         TErr: From<crate::QueryError>,
     {
         match &self.internals.engine {
-            ExecutionEngine::Real { connector, .. } => {
-                let new_tx_id = connector
-                    .executor
-                    .start_tx(
-                        connector.query_schema.clone(),
-                        EngineProtocol::Graphql,
-                        TransactionOptions::new(self.max_wait, self.timeout, self.isolation_level),
+            // already in a transaction, so nest inside it using a savepoint
+            ExecutionEngine::Real {
+                connector,
+                tx_id: Some(tx_id),
+                ..
+            } => {
+                // mongodb has no savepoints, so a nested transaction couldn't be rolled back on its own
+                if connector.provider == "mongodb" {
+                    return Err(QueryError::NestedTransactionUnsupported(connector.provider).into());
+                }
+
+                if self.timeout.is_some()
+                    || self.max_wait.is_some()
+                    || self.isolation_level.is_some()
+                {
+                    return Err(QueryError::InvalidArgument(
+                        "timeout, max wait and isolation level can't be set on a nested transaction"
+                            .to_string(),
                     )
-                    .await
-                    .map_err(|e| QueryError::Execute(e.into()))?;
+                    .into());
+                }
+
+                let depth = self.internals.transaction_depth + 1;
+                let savepoint = Savepoint {
+                    internals: self.internals,
+                    provider: connector.provider,
+                    name: format!("prisma_savepoint_{depth}"),
+                };
 
-                match tx(self.client.with_tx_id(Some(new_tx_id.clone()))).await {
+                savepoint.create().await?;
+
+                let mut tx_client = self.client.with_tx_id(Some(tx_id.clone()));
+                tx_client.internals_mut().transaction_depth = depth;
+                let pending_mutations = tx_client.internals().pending_mutations.clone();
+                let hooks = tx_client.internals().transaction_hooks.clone();
+
+                match tx(tx_client).await {
                     result @ Ok(_) => {
-                        connector
-                            .executor
-                            .commit_tx(new_tx_id)
-                            .await
-                            .map_err(|e| QueryError::Execute(e.into()))?;
+                        if let Err(e) = savepoint.release().await {
+                            TransactionHooks::finish(hooks, false);
+                            return Err(e.into());
+                        }
+
+                        // the outer transaction decides whether the mutations are committed
+                        if let (Some(outer), Some(inner)) =
+                            (&self.internals.pending_mutations, pending_mutations)
+                        {
+                            let mutations = std::mem::take(&mut *inner.lock().unwrap());
+                            outer.lock().unwrap().extend(mutations);
+                        }
+
+                        // and whether the hooks are run
+                        if let (Some(outer), Some(inner)) =
+                            (&self.internals.transaction_hooks, hooks)
+                        {
+                            inner.merge_into(outer);
+                        }
 
                         result
                     }
                     err @ Err(_) => {
-                        connector.executor.rollback_tx(new_tx_id).await.ok();
+                        let rollback = savepoint.rollback().await;
+
+                        TransactionHooks::finish(hooks, false);
+
+                        // the outer transaction would still contain the nested transaction's writes,
+                        // so the failed rollback is returned to stop it from being committed
+                        match rollback {
+                            Ok(()) => err,
+                            Err(e) => Err(e.into()),
+                        }
+                    }
+                }
+            }
+            ExecutionEngine::Real { connector, .. } => {
+                let new_tx_id = self.start(connector).await?;
+
+                let tx_client = self.client.with_tx_id(Some(new_tx_id.clone()));
+                let pending_mutations = tx_client.internals().pending_mutations.clone();
+                let hooks = tx_client.internals().transaction_hooks.clone();
+
+                match tx(tx_client).await {
+                    result @ Ok(_) => {
+                        let commit = connector.commit_tx(new_tx_id.clone()).await;
+
+                        self.internals.connection.finish_transaction(&new_tx_id);
+                        self.internals.metrics.transaction_finished(commit.is_ok());
+
+                        if let Err(e) = commit {
+                            TransactionHooks::finish(hooks, false);
+                            return Err(e.into());
+                        }
+
+                        self.internals
+                            .notify_committed_mutations(pending_mutations)
+                            .await;
+
+                        TransactionHooks::finish(hooks, true);
+
+                        result
+                    }
+                    err @ Err(_) => {
+                        connector.rollback_tx(new_tx_id.clone()).await.ok();
+
+                        self.internals.connection.finish_transaction(&new_tx_id);
+                        self.internals.metrics.transaction_finished(false);
+
+                        TransactionHooks::finish(hooks, false);
 
                         err
                     }
@@ -77,63 +199,354 @@ impl<'a, TClient: PrismaClient> TransactionBuilder<'a, TClient> {
         }
     }
 
+    /// Starts a top-level transaction, registering it so that `_disconnect` can roll it back.
+    async fn start(&self, connector: &ExecutorConnector) -> super::Result<TxId> {
+        self.internals.connection.check_connected()?;
+
+        let tx_id = connector.start_tx(self.options()).await?;
+
+        // the client was disconnected while the transaction was starting
+        if let Err(e) = self.internals.connection.start_transaction(tx_id.clone()) {
+            connector.rollback_tx(tx_id).await.ok();
+            return Err(e);
+        }
+
+        self.internals.metrics.transaction_started();
+
+        Ok(tx_id)
+    }
+
+    fn options(&self) -> TransactionOptions {
+        TransactionOptions::new(
+            self.max_wait.unwrap_or(2000),
+            self.timeout.unwrap_or(5000),
+            self.isolation_level.clone(),
+        )
+    }
+
     pub async fn begin(self) -> super::Result<(TransactionController<TClient>, TClient)> {
         Ok(match &self.internals.engine {
             ExecutionEngine::Real { connector, .. } => {
-                let new_tx_id = connector
-                    .executor
-                    .start_tx(
-                        connector.query_schema.clone(),
-                        EngineProtocol::Graphql,
-                        TransactionOptions::new(self.max_wait, self.timeout, self.isolation_level),
-                    )
-                    .await
-                    .map_err(|e| QueryError::Execute(e.into()))?;
+                let new_tx_id = self.start(connector).await?;
+
+                let tx_client = self.client.with_tx_id(Some(new_tx_id.clone()));
 
                 (
-                    TransactionController::new(new_tx_id.clone()),
-                    self.client.with_tx_id(Some(new_tx_id)),
+                    TransactionController::new(
+                        new_tx_id,
+                        Some(OpenTransaction {
+                            connector: connector.clone(),
+                            connection: self.internals.connection.clone(),
+                            metrics: self.internals.metrics.clone(),
+                            hooks: tx_client.internals().transaction_hooks.clone(),
+                        }),
+                    ),
+                    tx_client,
                 )
             }
             _ => (
-                TransactionController::new("".to_string().into()),
+                TransactionController::new("".to_string().into(), None),
                 self.client.with_tx_id(None),
             ),
         })
     }
 }
 
+/// A savepoint in an interactive transaction, used to run nested transactions.
+struct Savepoint<'a> {
+    internals: &'a PrismaClientInternals,
+    provider: &'static str,
+    name: String,
+}
+
+impl<'a> Savepoint<'a> {
+    async fn create(&self) -> super::Result<()> {
+        match self.provider {
+            "sqlserver" => self.execute(format!("SAVE TRANSACTION {}", self.name)),
+            _ => self.execute(format!("SAVEPOINT {}", self.name)),
+        }
+        .await
+    }
+
+    async fn release(&self) -> super::Result<()> {
+        match self.provider {
+            // sql server releases savepoints when the transaction ends
+            "sqlserver" => Ok(()),
+            _ => {
+                self.execute(format!("RELEASE SAVEPOINT {}", self.name))
+                    .await
+            }
+        }
+    }
+
+    async fn rollback(&self) -> super::Result<()> {
+        match self.provider {
+            "sqlserver" => self.execute(format!("ROLLBACK TRANSACTION {}", self.name)),
+            _ => self.execute(format!("ROLLBACK TO SAVEPOINT {}", self.name)),
+        }
+        .await
+    }
+
+    // executed on the engine directly so that middleware, metrics and tracing don't see it
+    async fn execute(&self, sql: String) -> super::Result<()> {
+        let (op, _) =
+            ExecuteRaw::new(self.internals, Raw::new(&sql, vec![]), self.provider).graphql();
+
+        self.internals.engine.execute(op, None).await.map(|_| ())
+    }
+}
+
+// not derived since TClient doesn't need to be Clone
+impl<'a, TClient> Clone for TransactionBuilder<'a, TClient> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client,
+            internals: self.internals,
+            timeout: self.timeout,
+            max_wait: self.max_wait,
+            isolation_level: self.isolation_level.clone(),
+        }
+    }
+}
+
+/// How long to wait before retrying a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backoff {
+    /// Retry immediately.
+    None,
+    /// Wait the same duration before every retry.
+    Fixed(Duration),
+    /// Wait `initial` before the first retry, doubling each time up to `max`.
+    Exponential { initial: Duration, max: Duration },
+}
+
+impl Backoff {
+    /// The time to wait before the given retry, starting at 1.
+    pub fn delay(&self, retry: u32) -> Duration {
+        match *self {
+            Self::None => Duration::ZERO,
+            Self::Fixed(delay) => delay,
+            Self::Exponential { initial, max } => initial
+                .checked_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+                .map_or(max, |delay| delay.min(max)),
+        }
+    }
+}
+
+/// An error that may succeed if the transaction that produced it is retried.
+pub trait RetryableError {
+    fn is_retryable(&self) -> bool;
+}
+
+impl RetryableError for QueryError {
+    fn is_retryable(&self) -> bool {
+        matches!(
+            self.kind(),
+            ErrorKind::TransactionConflict | ErrorKind::ConnectionFailed
+        )
+    }
+}
+
+/// Decides whether a transaction that failed with `TErr` should be retried.
+pub trait RetryCondition<TErr> {
+    fn should_retry(&self, error: &TErr) -> bool;
+}
+
+/// The default `RetryCondition`, which retries errors whose `RetryableError::is_retryable` is true.
+pub struct RetryableErrors;
+
+impl<TErr: RetryableError> RetryCondition<TErr> for RetryableErrors {
+    fn should_retry(&self, error: &TErr) -> bool {
+        error.is_retryable()
+    }
+}
+
+impl<TErr, F: Fn(&TErr) -> bool> RetryCondition<TErr> for F {
+    fn should_retry(&self, error: &TErr) -> bool {
+        self(error)
+    }
+}
+
+pub struct RetryingTransactionBuilder<'a, TClient, TCond> {
+    builder: TransactionBuilder<'a, TClient>,
+    retries: u32,
+    backoff: Backoff,
+    condition: TCond,
+}
+
+impl<'a, TClient: PrismaClient, TCond> RetryingTransactionBuilder<'a, TClient, TCond> {
+    /// Replaces the default retry condition with `condition`,
+    /// which is given the error returned by the failed attempt.
+    pub fn retry_if<TErr, F: Fn(&TErr) -> bool>(
+        self,
+        condition: F,
+    ) -> RetryingTransactionBuilder<'a, TClient, F> {
+        RetryingTransactionBuilder {
+            builder: self.builder,
+            retries: self.retries,
+            backoff: self.backoff,
+            condition,
+        }
+    }
+
+    /// Runs `tx` in a transaction, running it again in a new transaction
+    /// if it fails with an error that should be retried.
+    pub async fn run<TErr, TRet, TFut, TFn>(self, tx: TFn) -> Result<TRet, TErr>
+    where
+        TFut: Future<Output = Result<TRet, TErr>>,
+        TFn: Fn(TClient) -> TFut,
+        TErr: From<crate::QueryError>,
+        TCond: RetryCondition<TErr>,
+    {
+        let mut retry = 0;
+
+        loop {
+            match self.builder.clone().run(&tx).await {
+                Err(error) if retry < self.retries && self.condition.should_retry(&error) => {
+                    retry += 1;
+
+                    tokio::time::sleep(self.backoff.delay(retry)).await;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+type TransactionHook = Box<dyn FnOnce() + Send>;
+
+#[derive(Default)]
+struct TransactionHookLists {
+    on_commit: Vec<TransactionHook>,
+    on_rollback: Vec<TransactionHook>,
+}
+
+/// Hooks registered on a transaction's client with `_on_commit` and `_on_rollback`.
+#[derive(Default, Clone)]
+pub(crate) struct TransactionHooks(Arc<Mutex<TransactionHookLists>>);
+
+impl TransactionHooks {
+    pub fn on_commit(&self, hook: TransactionHook) {
+        self.0.lock().unwrap().on_commit.push(hook);
+    }
+
+    pub fn on_rollback(&self, hook: TransactionHook) {
+        self.0.lock().unwrap().on_rollback.push(hook);
+    }
+
+    /// Moves the hooks of a nested transaction to the transaction it was nested in.
+    fn merge_into(self, outer: &Self) {
+        let inner = std::mem::take(&mut *self.0.lock().unwrap());
+
+        let mut outer = outer.0.lock().unwrap();
+        outer.on_commit.extend(inner.on_commit);
+        outer.on_rollback.extend(inner.on_rollback);
+    }
+
+    /// Runs either the commit or rollback hooks, discarding the others.
+    fn finish(hooks: Option<Self>, committed: bool) {
+        if let Some(hooks) = hooks {
+            let lists = std::mem::take(&mut *hooks.0.lock().unwrap());
+
+            let hooks = match committed {
+                true => lists.on_commit,
+                false => lists.on_rollback,
+            };
+
+            for hook in hooks {
+                hook();
+            }
+        }
+    }
+}
+
+/// A transaction started with `begin` that hasn't been committed or rolled back yet.
+struct OpenTransaction {
+    connector: Arc<ExecutorConnector>,
+    connection: Arc<ConnectionState>,
+    metrics: Arc<MetricsRecorder>,
+    hooks: Option<TransactionHooks>,
+}
+
+impl OpenTransaction {
+    async fn rollback(self, tx_id: TxId) {
+        self.connector.rollback_tx(tx_id.clone()).await.ok();
+
+        self.connection.finish_transaction(&tx_id);
+        self.metrics.transaction_finished(false);
+
+        TransactionHooks::finish(self.hooks, false);
+    }
+}
+
 pub struct TransactionController<TClient> {
     tx_id: TxId,
+    // taken when committing or rolling back, so that dropping an open transaction rolls it back
+    open: Option<OpenTransaction>,
     _client: PhantomData<TClient>,
 }
 
 impl<TClient: PrismaClient> TransactionController<TClient> {
-    fn new(tx_id: TxId) -> Self {
+    fn new(tx_id: TxId, open: Option<OpenTransaction>) -> Self {
         Self {
             tx_id,
+            open,
             _client: Default::default(),
         }
     }
 
-    pub async fn commit(self, client: TClient) -> super::Result<()> {
-        Ok(match &client.internals().engine {
-            ExecutionEngine::Real { connector, .. } => connector
-                .executor
-                .commit_tx(self.tx_id)
-                .await
-                .map_err(|e| QueryError::Execute(e.into()))?,
-            _ => {}
-        })
+    pub async fn commit(mut self, client: TClient) -> super::Result<()> {
+        let internals = client.internals();
+
+        let hooks = match self.open.take() {
+            Some(open) => {
+                let commit = open.connector.commit_tx(self.tx_id.clone()).await;
+
+                open.connection.finish_transaction(&self.tx_id);
+                open.metrics.transaction_finished(commit.is_ok());
+
+                if let Err(e) = commit {
+                    TransactionHooks::finish(open.hooks, false);
+                    return Err(e);
+                }
+
+                open.hooks
+            }
+            None => None,
+        };
+
+        internals
+            .notify_committed_mutations(internals.pending_mutations.clone())
+            .await;
+
+        TransactionHooks::finish(hooks, true);
+
+        Ok(())
     }
 
-    pub async fn rollback(self, client: TClient) -> super::Result<()> {
-        Ok(match &client.internals().engine {
-            ExecutionEngine::Real { connector, .. } => {
-                connector.executor.rollback_tx(self.tx_id).await.ok();
+    pub async fn rollback(mut self, _client: TClient) -> super::Result<()> {
+        if let Some(open) = self.open.take() {
+            open.rollback(self.tx_id.clone()).await;
+        }
+
+        Ok(())
+    }
+}
+
+impl<TClient> Drop for TransactionController<TClient> {
+    fn drop(&mut self) {
+        if let Some(open) = self.open.take() {
+            match tokio::runtime::Handle::try_current() {
+                Ok(handle) => {
+                    handle.spawn(open.rollback(self.tx_id.clone()));
+                }
+                Err(_) => tracing::warn!(
+                    tx_id = %self.tx_id,
+                    "TransactionController dropped outside of a Tokio runtime, \
+                    the transaction will remain open until it times out"
+                ),
             }
-            _ => {}
-        })
+        }
     }
 }
 
@@ -101,36 +101,6 @@ impl<'a> GenerateArgs<'a> {
                 }));
             }
 
-            // for i in 0..dml.models.len() {
-            //     let m = &dml.models[i];
-            //     let p =
-            //         match schema.find_input_type(&(m.name.to_string() + "OrderByRelevanceInput")) {
-            //             Some(p) => p,
-            //             None => continue,
-            //         };
-
-            //     let mut methods = vec![];
-
-            //     for field in &p.fields {
-            //         if let Some(method) = input_field_as_method(field) {
-            //             methods.push(method);
-            //         }
-            //     }
-
-            //     filters.push(Filter {
-            //         name: m.name.clone(),
-            //         methods,
-            //     });
-
-            //     dml.models[i]
-            //         .fields
-            //         .push(Field::ScalarField(ScalarField::new(
-            //             "relevance",
-            //             FieldArity::Optional,
-            //             FieldType::Enum(p.name.clone()),
-            //         )));
-            // }
-
             filters
         };
 
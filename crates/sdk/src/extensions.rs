@@ -288,6 +288,13 @@ impl DmmfTypeReferenceExt for DmmfTypeReference {
 
                         quote!(#model_name_snake::OrderByWithRelationParam)
                     }
+                    t if t.ends_with("OrderByWithRelationAndSearchRelevanceInput") => {
+                        let model_name =
+                            t.replace("OrderByWithRelationAndSearchRelevanceInput", "");
+                        let model_name_snake = snake_ident(&model_name);
+
+                        quote!(#model_name_snake::OrderByWithRelationParam)
+                    }
                     t if t.ends_with("OrderByRelationAggregateInput") => {
                         let model_name = t.replace("OrderByRelationAggregateInput", "");
                         let model_name_snake = snake_ident(&model_name);
@@ -0,0 +1,97 @@
+use crate::{db::*, utils::*};
+
+async fn create_posts(client: &PrismaClient) -> TestResult {
+    client
+        .post()
+        .create_many(vec![
+            post::create_unchecked("Post 1".to_string(), true, vec![post::views::set(10)]),
+            post::create_unchecked("Post 2".to_string(), true, vec![post::views::set(20)]),
+            post::create_unchecked("Post 3".to_string(), false, vec![post::views::set(60)]),
+        ])
+        .exec()
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn count_all() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client).await?;
+
+    let result = client.post().aggregate(vec![]).exec().await?;
+
+    assert_eq!(result.count._all, Some(3));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn functions() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client).await?;
+
+    let result = client
+        .post()
+        .aggregate(vec![])
+        .select(vec![
+            post::count_all(),
+            post::desc::count(),
+            post::views::avg(),
+            post::views::sum(),
+            post::views::min(),
+            post::views::max(),
+            post::title::max(),
+        ])
+        .exec()
+        .await?;
+
+    assert_eq!(result.count._all, Some(3));
+    assert_eq!(result.count.desc, Some(0));
+    assert_eq!(result.avg.views, Some(30.0));
+    assert_eq!(result.sum.views, Some(90));
+    assert_eq!(result.min.views, Some(10));
+    assert_eq!(result.max.views, Some(60));
+    assert_eq!(result.max.title.as_deref(), Some("Post 3"));
+    assert_eq!(result.min.title, None);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn where_() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client).await?;
+
+    let result = client
+        .post()
+        .aggregate(vec![post::published::equals(true)])
+        .select(vec![post::views::sum()])
+        .exec()
+        .await?;
+
+    assert_eq!(result.sum.views, Some(30));
+    assert_eq!(result.count._all, None);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn no_results() -> TestResult {
+    let client = client().await;
+
+    let result = client
+        .post()
+        .aggregate(vec![])
+        .select(vec![post::views::avg(), post::views::max()])
+        .exec()
+        .await?;
+
+    assert_eq!(result.avg.views, None);
+    assert_eq!(result.max.views, None);
+
+    cleanup(client).await
+}
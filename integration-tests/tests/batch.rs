@@ -1,5 +1,5 @@
 #![allow(unused_must_use)]
-use prisma_client_rust::prisma_errors::query_engine::UniqueKeyViolation;
+use prisma_client_rust::{prisma_errors::query_engine::UniqueKeyViolation, BatchOptions};
 
 use crate::db::*;
 use crate::utils::*;
@@ -373,3 +373,53 @@ async fn allows_empty() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn or_throw() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    let found = client
+        ._batch(vec![client
+            .user()
+            .find_unique_or_throw(user::id::equals(user.id.clone()))])
+        .await?;
+    assert_eq!(found[0].id, user.id);
+
+    let error = client
+        ._batch(vec![client
+            .user()
+            .find_unique_or_throw(user::id::equals("missing".to_string()))])
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        prisma_client_rust::QueryError::RecordNotFound(_)
+    ));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn with_options() -> TestResult {
+    let client = client().await;
+
+    let users = client
+        ._batch_with_options(
+            vec![
+                client.user().create("Brendan".to_string(), vec![]),
+                client.user().create("Oscar".to_string(), vec![]),
+            ],
+            BatchOptions::default().with_isolation_level(TransactionIsolationLevel::Serializable),
+        )
+        .await?;
+
+    assert_eq!(users.len(), 2);
+
+    cleanup(client).await
+}
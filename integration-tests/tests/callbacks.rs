@@ -1,67 +1,201 @@
-use prisma_client_rust::{ModelMutationCallbackData, ModelMutationType};
+use prisma_client_rust::{ModelMutationCallbackData, ModelWriteOperation};
 use std::sync::{Arc, Mutex};
 
 use crate::db::*;
 use crate::utils::*;
 
+type Mutations = Arc<Mutex<Vec<ModelMutationCallbackData>>>;
+
+async fn client_with_callback() -> (PrismaClient, Mutations) {
+    let mutations: Mutations = Default::default();
+
+    let callback_mutations = mutations.clone();
+
+    let client = PrismaClient::_builder()
+        .with_model_mutation_callback(move |data| callback_mutations.lock().unwrap().push(data))
+        .build()
+        .await
+        .unwrap();
+
+    (client, mutations)
+}
+
+fn actions(mutations: &Mutations) -> Vec<(String, ModelWriteOperation)> {
+    mutations
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|data| (data.model.clone(), data.action))
+        .collect()
+}
+
 #[tokio::test]
 async fn mutation() -> TestResult {
-    let client = client().await;
-
-    let callback_data = {
-        let callback_data = Arc::new(Mutex::new(vec![]));
-
-        let callback_callback_data = callback_data.clone();
-
-        let client = PrismaClient::_builder()
-            .with_model_mutation_callback(move |data| {
-                callback_callback_data.lock().unwrap().push(data)
-            })
-            .build()
-            .await
-            .unwrap();
-
-        let user = client
-            .user()
-            .create("Brendan".to_string(), vec![])
-            .exec()
-            .await
-            .unwrap();
-
-        client
-            .user()
-            .update(user::id::equals(user.id.clone()), vec![])
-            .exec()
-            .await
-            .unwrap();
-
-        client
-            .user()
-            .delete(user::id::equals(user.id))
-            .exec()
-            .await
-            .unwrap();
-
-        callback_data
-    };
+    cleanup(client().await).await?;
+
+    let (client, mutations) = client_with_callback().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    client
+        .user()
+        .update(
+            user::id::equals(user.id.clone()),
+            vec![user::name::set("Oscar".to_string())],
+        )
+        .exec()
+        .await?;
+
+    client
+        .user()
+        .delete(user::id::equals(user.id.clone()))
+        .exec()
+        .await?;
+
+    client.user().find_many(vec![]).exec().await?;
+
+    assert_eq!(
+        actions(&mutations),
+        vec![
+            ("User".to_string(), ModelWriteOperation::Create),
+            ("User".to_string(), ModelWriteOperation::Update),
+            ("User".to_string(), ModelWriteOperation::Delete),
+        ]
+    );
+
+    let updated = mutations.lock().unwrap()[1]
+        .record::<user::Data>()
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.id, user.id);
+    assert_eq!(updated.name, "Oscar");
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn many() -> TestResult {
+    cleanup(client().await).await?;
+
+    let (client, mutations) = client_with_callback().await;
+
+    client
+        ._batch((
+            client.user().create("Brendan".to_string(), vec![]),
+            client.user().create("Oscar".to_string(), vec![]),
+        ))
+        .await?;
+
+    client.user().delete_many(vec![]).exec().await?;
 
     assert_eq!(
-        &*callback_data.lock().unwrap(),
-        &vec![
-            ModelMutationCallbackData {
-                action: ModelMutationType::Create,
-                model: "User"
-            },
-            ModelMutationCallbackData {
-                action: ModelMutationType::Update,
-                model: "User"
-            },
-            ModelMutationCallbackData {
-                action: ModelMutationType::Delete,
-                model: "User"
-            },
+        actions(&mutations),
+        vec![
+            ("User".to_string(), ModelWriteOperation::Create),
+            ("User".to_string(), ModelWriteOperation::Create),
+            ("User".to_string(), ModelWriteOperation::DeleteMany),
         ]
     );
+    assert_eq!(mutations.lock().unwrap()[2].count(), 2);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn transaction() -> TestResult {
+    cleanup(client().await).await?;
+
+    let (client, mutations) = client_with_callback().await;
+
+    let result: TestResult = client
+        ._transaction()
+        .run(|client| async move {
+            client
+                .user()
+                .create(
+                    "Brendan".to_string(),
+                    vec![user::email::set(Some("brendan@example.com".to_string()))],
+                )
+                .exec()
+                .await?;
+
+            // violates the unique constraint on email
+            client
+                .user()
+                .create(
+                    "Oscar".to_string(),
+                    vec![user::email::set(Some("brendan@example.com".to_string()))],
+                )
+                .exec()
+                .await?;
+
+            Ok(())
+        })
+        .await;
+    assert!(result.is_err());
+    assert!(actions(&mutations).is_empty());
+
+    let tx_mutations = mutations.clone();
+
+    client
+        ._transaction()
+        .run(|client| async move {
+            client
+                .user()
+                .create("Brendan".to_string(), vec![])
+                .exec()
+                .await?;
+
+            assert!(actions(&tx_mutations).is_empty());
+
+            TestResult::Ok(())
+        })
+        .await?;
+
+    assert_eq!(
+        actions(&mutations),
+        vec![("User".to_string(), ModelWriteOperation::Create)]
+    );
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn async_callback() -> TestResult {
+    cleanup(client().await).await?;
+
+    let mutations: Mutations = Default::default();
+
+    let callback_mutations = mutations.clone();
+
+    let client = PrismaClient::_builder()
+        .with_async_model_mutation_callback(move |data| {
+            let mutations = callback_mutations.clone();
+
+            async move {
+                tokio::task::yield_now().await;
+                mutations.lock().unwrap().push(data);
+            }
+        })
+        .build()
+        .await
+        .unwrap();
+
+    client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    // the create doesn't resolve until the callback has finished
+    assert_eq!(
+        actions(&mutations),
+        vec![("User".to_string(), ModelWriteOperation::Create)]
+    );
 
     cleanup(client).await
 }
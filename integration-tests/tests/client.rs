@@ -0,0 +1,362 @@
+use std::{path::Path, time::Duration};
+
+use futures::future::BoxFuture;
+use prisma_client_rust::{
+    raw, serde_value, BatchOptions, ConnectionOptions, ErrorKind, Middleware, NewClientError, Next,
+    NextBatch, QueryContext, QueryError, SqliteJournalMode,
+};
+
+use crate::{db::*, utils::*};
+
+#[tokio::test]
+async fn connection_options() -> TestResult {
+    let client = PrismaClient::_builder()
+        .connection_limit(4)
+        .socket_timeout(Duration::from_secs(10))
+        .build()
+        .await
+        .unwrap();
+
+    let config = client._connection_config();
+
+    assert_eq!(config.provider, "sqlite");
+    assert!(config.url.contains("connection_limit=4"));
+    assert!(config.url.contains("socket_timeout=10"));
+    assert_eq!(
+        config.options,
+        ConnectionOptions {
+            connection_limit: Some(4),
+            socket_timeout: Some(Duration::from_secs(10)),
+            ..Default::default()
+        }
+    );
+
+    client.user().find_many(vec![]).exec().await?;
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn sqlite_journal_mode() -> TestResult {
+    let client = PrismaClient::_builder()
+        .sqlite_journal_mode(SqliteJournalMode::Wal)
+        .build()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        client._connection_config().options.sqlite_journal_mode,
+        Some(SqliteJournalMode::Wal)
+    );
+
+    let mode: Vec<serde_json::Value> = client
+        ._query_raw(raw!("PRAGMA journal_mode"))
+        .exec()
+        .await?;
+    assert_eq!(mode[0]["journal_mode"], "wal");
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn sqlite_path_relative_to_schema() -> TestResult {
+    let client = client().await;
+
+    let url = client._connection_config().url;
+    let path = url.strip_prefix("file:").unwrap();
+
+    // the schema's `file:dev.db` is resolved against the schema's directory
+    assert!(Path::new(path).is_absolute());
+    assert!(Path::new(path).ends_with("integration-tests/dev.db"));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn invalid_connection_options() {
+    let error = PrismaClient::_builder()
+        .statement_cache_size(100)
+        .build()
+        .await
+        .unwrap_err();
+    assert!(matches!(error, NewClientError::InvalidConnectionOption(_)));
+
+    let error = PrismaClient::_builder()
+        .connection_limit(0)
+        .build()
+        .await
+        .unwrap_err();
+    assert!(matches!(error, NewClientError::InvalidConnectionOption(_)));
+
+    let error = PrismaClient::_builder()
+        .socket_timeout(Duration::from_millis(1500))
+        .build()
+        .await
+        .unwrap_err();
+    assert!(matches!(error, NewClientError::InvalidConnectionOption(_)));
+}
+
+#[tokio::test]
+async fn replicas() -> TestResult {
+    let client = client().await;
+
+    let url = client._connection_config().url;
+    let dir = Path::new(url.strip_prefix("file:").unwrap())
+        .parent()
+        .unwrap()
+        .to_path_buf();
+
+    // sqlite has no replicas, so copies of the database with a different user in each are used as them,
+    // which shows which database each query ran on
+    let mut replica_paths = vec![];
+    for name in ["replica 1", "replica 2"] {
+        let path = dir.join(format!("{}.db", name.replace(' ', "-")));
+        std::fs::remove_file(&path).ok();
+
+        let user = client
+            .user()
+            .create(name.to_string(), vec![])
+            .exec()
+            .await?;
+        client
+            ._execute_raw(raw!(&format!("VACUUM INTO '{}'", path.display())))
+            .exec()
+            .await?;
+        client
+            .user()
+            .delete(user::id::equals(user.id))
+            .exec()
+            .await?;
+
+        replica_paths.push(path);
+    }
+
+    let replica_urls = replica_paths
+        .iter()
+        .map(|path| format!("file:{}", path.display()))
+        .collect::<Vec<_>>();
+
+    let client = PrismaClient::_builder()
+        .with_replica_url(replica_urls[0].clone())
+        .with_replica_url(replica_urls[1].clone())
+        .build()
+        .await
+        .unwrap();
+
+    assert_eq!(client._connection_config().replica_urls, replica_urls);
+
+    // checks the primary and the replicas
+    client._health_check().await?;
+
+    let names = |users: Vec<user::Data>| users.into_iter().map(|u| u.name).collect::<Vec<_>>();
+
+    // reads are round-robined across the replicas
+    for expected in ["replica 1", "replica 2", "replica 1"] {
+        let users = client.user().find_many(vec![]).exec().await?;
+        assert_eq!(names(users), vec![expected]);
+    }
+
+    // writes and raw executes go to the primary
+    client
+        .user()
+        .create("primary".to_string(), vec![])
+        .exec()
+        .await?;
+    let count = client
+        ._execute_raw(raw!("UPDATE User SET name = 'primary (raw)'"))
+        .exec()
+        .await?;
+    assert_eq!(count, 1);
+
+    // as do reads with `_primary`
+    let users = client._primary().user().find_many(vec![]).exec().await?;
+    assert_eq!(names(users), vec!["primary (raw)"]);
+
+    // and reads in transactions
+    let users = client
+        ._transaction()
+        .run(|tx| async move { tx.user().find_many(vec![]).exec().await })
+        .await?;
+    assert_eq!(names(users), vec!["primary (raw)"]);
+
+    // the replicas weren't written to
+    for expected in ["replica 2", "replica 1"] {
+        let users = client.user().find_many(vec![]).exec().await?;
+        assert_eq!(names(users), vec![expected]);
+    }
+
+    let result = cleanup(client).await;
+
+    for path in replica_paths {
+        std::fs::remove_file(path).ok();
+    }
+
+    result
+}
+
+#[tokio::test]
+async fn lazy_connect() -> TestResult {
+    let client = PrismaClient::_builder()
+        .lazy_connect()
+        .build()
+        .await
+        .unwrap();
+
+    client._connect().await?;
+    client._health_check().await?;
+
+    client.user().find_many(vec![]).exec().await?;
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn lazy_connect_retry() -> TestResult {
+    let dir = std::env::temp_dir().join(format!("prisma-lazy-connect-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    // the database's directory doesn't exist yet, so connecting fails
+    let client = PrismaClient::_builder()
+        .with_url(format!("file:{}", dir.join("dev.db").display()))
+        .lazy_connect()
+        .build()
+        .await
+        .unwrap();
+
+    assert!(client._connect().await.is_err());
+
+    std::fs::create_dir_all(&dir).unwrap();
+
+    client._connect().await?;
+    client._health_check().await?;
+
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn disconnect() -> TestResult {
+    let client = client().await;
+
+    let (in_flight, _) = tokio::join!(client.user().find_many(vec![]).exec(), async {
+        tokio::task::yield_now().await;
+        client._disconnect().await
+    });
+    assert!(in_flight.is_ok());
+
+    let error = client.user().find_many(vec![]).exec().await.unwrap_err();
+    assert!(matches!(error, QueryError::Disconnected));
+    assert_eq!(error.kind(), ErrorKind::ConnectionFailed);
+
+    assert!(client._transaction().begin().await.is_err());
+    assert!(matches!(
+        client._connect().await,
+        Err(QueryError::Disconnected)
+    ));
+
+    // the disconnected client can't be used to clean up
+    cleanup(crate::utils::client().await).await
+}
+
+#[tokio::test]
+async fn disconnect_open_transaction() -> TestResult {
+    let client = client().await;
+
+    let (tx, tx_client) = client._transaction().begin().await?;
+
+    tx_client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    client._disconnect().await;
+
+    assert!(tx_client.user().find_many(vec![]).exec().await.is_err());
+    assert!(tx.commit(tx_client).await.is_err());
+
+    // the transaction was rolled back,
+    // checked with a new client that doesn't clean up the database first
+    let client = PrismaClient::_builder().build().await.unwrap();
+    assert_eq!(client.user().count(vec![]).exec().await?, 0);
+
+    cleanup(client).await
+}
+
+/// Delays every query so that it can be timed out.
+struct Delay(Duration);
+
+impl Middleware for Delay {
+    fn handle<'a>(
+        &'a self,
+        ctx: QueryContext,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, prisma_client_rust::Result<serde_value::Value>> {
+        Box::pin(async move {
+            tokio::time::sleep(self.0).await;
+            next.run(ctx).await
+        })
+    }
+
+    fn handle_batch<'a>(
+        &'a self,
+        ctxs: Vec<QueryContext>,
+        next: NextBatch<'a>,
+    ) -> BoxFuture<
+        'a,
+        prisma_client_rust::Result<Vec<prisma_client_rust::Result<serde_value::Value>>>,
+    > {
+        Box::pin(async move {
+            tokio::time::sleep(self.0).await;
+            next.run(ctxs).await
+        })
+    }
+}
+
+#[tokio::test]
+async fn timeout() -> TestResult {
+    cleanup(client().await).await?;
+
+    let client = PrismaClient::_builder()
+        .with_middleware(Delay(Duration::from_millis(200)))
+        .query_timeout(Duration::from_millis(50))
+        .build()
+        .await
+        .unwrap();
+
+    let error = client.user().find_many(vec![]).exec().await.unwrap_err();
+    assert!(matches!(error, QueryError::Timeout(_)));
+    assert_eq!(error.kind(), ErrorKind::Timeout);
+
+    let error = client
+        ._batch_with_options(
+            vec![client.user().find_many(vec![])],
+            BatchOptions::default().with_timeout(Duration::from_millis(10)),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(error, QueryError::Timeout(_)));
+
+    // a query's own timeout overrides the client's default
+    client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .timeout(Duration::from_secs(5))
+        .exec()
+        .await?;
+
+    // the client is still usable after queries time out
+    let users = client
+        .user()
+        .find_many(vec![])
+        .select(user::select!({ name }))
+        .timeout(Duration::from_secs(5))
+        .exec()
+        .await?;
+    assert_eq!(users.len(), 1);
+
+    assert_eq!(client._metrics().errors.get("timeout"), Some(&2));
+
+    cleanup(crate::utils::client().await).await
+}
@@ -0,0 +1,99 @@
+use prisma_client_rust::{ErrorKind, ModelOperation, ModelWriteOperation, QueryError};
+
+use crate::{db::*, utils::*};
+
+#[tokio::test]
+async fn unique_constraint_violation() -> TestResult {
+    let client = client().await;
+
+    client
+        .user()
+        .create(
+            "Brendan".to_string(),
+            vec![user::email::set(Some("brendan@example.com".to_string()))],
+        )
+        .exec()
+        .await?;
+
+    let error = client
+        .user()
+        .create(
+            "Oscar".to_string(),
+            vec![user::email::set(Some("brendan@example.com".to_string()))],
+        )
+        .exec()
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error.kind(),
+        ErrorKind::UniqueConstraintViolation {
+            target: vec!["email".to_string()]
+        }
+    );
+    assert_eq!(error.model(), Some("User"));
+    assert_eq!(
+        error.action(),
+        Some(ModelOperation::Write(ModelWriteOperation::Create))
+    );
+
+    assert_eq!(error.http_status(), 409);
+
+    let body = error.response_body();
+    assert_eq!(body.kind, "unique_constraint_violation");
+    assert_eq!(body.code.as_deref(), Some("P2002"));
+    assert_eq!(body.target, Some(vec!["email".to_string()]));
+    assert_eq!(body.message, "Unique constraint violated (P2002)");
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn foreign_key_violation() -> TestResult {
+    let client = client().await;
+
+    let error = client
+        .post()
+        .create_unchecked(
+            "Post".to_string(),
+            true,
+            vec![post::author_id::set(Some("missing".to_string()))],
+        )
+        .exec()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::ForeignKeyViolation { .. }
+    ));
+    assert_eq!(error.http_status(), 422);
+
+    let body = error.response_body();
+    assert_eq!(body.kind, "foreign_key_violation");
+    assert_eq!(body.code.as_deref(), Some("P2003"));
+    assert_eq!(body.message, "Foreign key constraint violated (P2003)");
+    assert!(!body.message.contains("missing"));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn record_not_found() -> TestResult {
+    let client = client().await;
+
+    let error = client
+        .post()
+        .delete(post::id::equals("missing".to_string()))
+        .exec()
+        .await
+        .unwrap_err();
+
+    // only the *_or_throw queries return QueryError::RecordNotFound
+    assert!(matches!(error, QueryError::Execute(_)));
+    assert_eq!(error.kind(), ErrorKind::RecordNotFound);
+    assert_eq!(error.model(), Some("Post"));
+    assert_eq!(error.http_status(), 404);
+
+    cleanup(client).await
+}
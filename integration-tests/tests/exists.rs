@@ -0,0 +1,35 @@
+use crate::{db::*, utils::*};
+
+#[tokio::test]
+async fn exists() -> TestResult {
+    let client = client().await;
+
+    client
+        .post()
+        .create("My post title!".to_string(), false, vec![])
+        .exec()
+        .await?;
+
+    let exists = client
+        .post()
+        .exists(vec![post::title::equals("My post title!".to_string())])
+        .exec()
+        .await?;
+    assert!(exists);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn not_exists() -> TestResult {
+    let client = client().await;
+
+    let exists = client
+        .post()
+        .exists(vec![post::title::equals("My post title!".to_string())])
+        .exec()
+        .await?;
+    assert!(!exists);
+
+    cleanup(client).await
+}
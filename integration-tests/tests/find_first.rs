@@ -1,4 +1,4 @@
-use prisma_client_rust::{and, not, or};
+use prisma_client_rust::{and, not, or, prisma_errors::query_engine::RecordRequiredButNotFound};
 
 use crate::{db::*, utils::*};
 
@@ -356,3 +356,31 @@ async fn list_wrapper_query_transformation() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn or_throw() -> TestResult {
+    let client = client().await;
+
+    client
+        .post()
+        .create("My post title!".to_string(), false, vec![])
+        .exec()
+        .await?;
+
+    let found = client
+        .post()
+        .find_first_or_throw(vec![post::title::equals("My post title!".to_string())])
+        .exec()
+        .await?;
+    assert_eq!(found.title, "My post title!");
+
+    let error = client
+        .post()
+        .find_first_or_throw(vec![post::published::equals(true)])
+        .exec()
+        .await
+        .unwrap_err();
+    assert!(error.is_prisma_error::<RecordRequiredButNotFound>());
+
+    cleanup(client).await
+}
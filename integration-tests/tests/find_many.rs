@@ -1,4 +1,5 @@
-use prisma_client_rust::or;
+use futures::TryStreamExt;
+use prisma_client_rust::{or, QueryError};
 
 use crate::{db::*, utils::*};
 
@@ -142,6 +143,176 @@ async fn cursor() -> TestResult {
     cleanup(client).await
 }
 
+#[tokio::test]
+async fn stream() -> TestResult {
+    let client = client().await;
+
+    for i in 1..=5 {
+        client
+            .post()
+            .create(format!("Foo {i}"), i % 2 == 0, vec![])
+            .exec()
+            .await?;
+    }
+
+    let found = client
+        .post()
+        .find_many(vec![])
+        .order_by(post::title::order(SortOrder::Asc))
+        .stream(2)
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(
+        found.iter().map(|p| p.title.as_str()).collect::<Vec<_>>(),
+        ["Foo 1", "Foo 2", "Foo 3", "Foo 4", "Foo 5"]
+    );
+
+    let found = client
+        .post()
+        .find_many(vec![post::published::equals(false)])
+        .order_by(post::title::order(SortOrder::Desc))
+        .skip(1)
+        .take(3)
+        .stream(1)
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(
+        found.iter().map(|p| p.title.as_str()).collect::<Vec<_>>(),
+        ["Foo 3", "Foo 1"]
+    );
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn paginate() -> TestResult {
+    let client = client().await;
+
+    for i in 1..=5 {
+        client
+            .post()
+            .create(format!("Foo {i}"), i % 2 == 0, vec![])
+            .exec()
+            .await?;
+    }
+
+    let page = client
+        .post()
+        .find_many(vec![])
+        .order_by(post::title::order(SortOrder::Asc))
+        .paginate(2, 2)
+        .await?;
+    assert_eq!(page.total, 5);
+    assert!(page.has_more);
+    assert_eq!(
+        page.items
+            .iter()
+            .map(|p| p.title.as_str())
+            .collect::<Vec<_>>(),
+        ["Foo 3", "Foo 4"]
+    );
+
+    let page = client
+        .post()
+        .find_many(vec![])
+        .order_by(post::title::order(SortOrder::Asc))
+        .paginate(3, 2)
+        .await?;
+    assert!(!page.has_more);
+    assert!(page.next_cursor.is_none());
+    assert_eq!(page.items.len(), 1);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn paginate_distinct() -> TestResult {
+    let client = client().await;
+
+    for i in 1..=5 {
+        client
+            .post()
+            .create(format!("Foo {i}"), i % 2 == 0, vec![])
+            .exec()
+            .await?;
+    }
+
+    let page = client
+        .post()
+        .find_many(vec![])
+        .distinct(vec![post::ScalarField::Published])
+        .paginate(1, 1)
+        .await?;
+    assert_eq!(page.total, 2);
+    assert!(page.has_more);
+    assert_eq!(page.items.len(), 1);
+
+    let page = client
+        .post()
+        .find_many(vec![])
+        .distinct(vec![post::ScalarField::Published])
+        .paginate(2, 1)
+        .await?;
+    assert_eq!(page.total, 2);
+    assert!(!page.has_more);
+    assert_eq!(page.items.len(), 1);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn paginate_invalid_per_page() -> TestResult {
+    let client = client().await;
+
+    for per_page in [0, -1] {
+        let error = client
+            .post()
+            .find_many(vec![])
+            .paginate(1, per_page)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, QueryError::InvalidArgument(_)));
+    }
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn paginate_after() -> TestResult {
+    let client = client().await;
+
+    for i in 1..=5 {
+        client
+            .post()
+            .create(format!("Foo {i}"), i % 2 == 0, vec![])
+            .exec()
+            .await?;
+    }
+
+    let mut titles = vec![];
+    let mut cursor = None;
+
+    loop {
+        let page = client
+            .post()
+            .find_many(vec![post::published::equals(false)])
+            .order_by(post::title::order(SortOrder::Asc))
+            .paginate_after(cursor, 2)
+            .await?;
+        assert_eq!(page.total, 3);
+
+        titles.extend(page.items.into_iter().map(|p| p.title));
+
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    assert_eq!(titles, ["Foo 1", "Foo 3", "Foo 5"]);
+
+    cleanup(client).await
+}
+
 // From Spacedrive
 #[tokio::test]
 async fn cursor_order() -> TestResult {
@@ -428,6 +599,40 @@ async fn ordering() -> TestResult {
     cleanup(client).await
 }
 
+#[tokio::test]
+async fn distinct() -> TestResult {
+    let client = client().await;
+
+    client
+        .post()
+        .create("Test post 1".to_string(), false, vec![])
+        .exec()
+        .await?;
+    client
+        .post()
+        .create("Test post 2".to_string(), false, vec![])
+        .exec()
+        .await?;
+    client
+        .post()
+        .create("Test post 3".to_string(), true, vec![])
+        .exec()
+        .await?;
+
+    let found = client
+        .post()
+        .find_many(vec![post::title::contains("Test".to_string())])
+        .distinct(vec![post::ScalarField::Published])
+        .order_by(post::published::order(SortOrder::Asc))
+        .exec()
+        .await?;
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[0].published, false);
+    assert_eq!(found[1].published, true);
+
+    cleanup(client).await
+}
+
 #[tokio::test]
 async fn select() -> TestResult {
     let client = client().await;
@@ -101,3 +101,34 @@ async fn no_match() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn or_throw() -> TestResult {
+    let client = client().await;
+
+    let post = client
+        .post()
+        .create("My post title!".to_string(), false, vec![])
+        .exec()
+        .await?;
+
+    let found = client
+        .post()
+        .find_unique_or_throw(post::id::equals(post.id.clone()))
+        .exec()
+        .await?;
+    assert_eq!(found.id, post.id);
+
+    let error = client
+        .post()
+        .find_unique_or_throw(post::id::equals("missing".to_string()))
+        .exec()
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        prisma_client_rust::QueryError::RecordNotFound(_)
+    ));
+
+    cleanup(client).await
+}
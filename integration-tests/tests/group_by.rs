@@ -0,0 +1,168 @@
+use crate::{db::*, utils::*};
+
+async fn create_posts(client: &PrismaClient) -> TestResult {
+    client
+        .post()
+        .create_many(vec![
+            post::create_unchecked("Post 1".to_string(), true, vec![post::views::set(10)]),
+            post::create_unchecked("Post 2".to_string(), true, vec![post::views::set(20)]),
+            post::create_unchecked("Post 3".to_string(), false, vec![post::views::set(60)]),
+        ])
+        .exec()
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn by_field() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client).await?;
+
+    let groups = client
+        .post()
+        .group_by(vec![post::ScalarField::Published], vec![])
+        .select(vec![post::count_all(), post::views::sum()])
+        .order_by(post::published::order(SortOrder::Asc))
+        .exec()
+        .await?;
+
+    assert_eq!(groups.len(), 2);
+
+    assert_eq!(groups[0].published, Some(false));
+    assert_eq!(groups[0]._count._all, Some(1));
+    assert_eq!(groups[0]._sum.views, Some(60));
+    assert_eq!(groups[0].title, None);
+
+    assert_eq!(groups[1].published, Some(true));
+    assert_eq!(groups[1]._count._all, Some(2));
+    assert_eq!(groups[1]._sum.views, Some(30));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn having() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client).await?;
+
+    let groups = client
+        .post()
+        .group_by(vec![post::ScalarField::Published], vec![])
+        .select(vec![post::views::avg()])
+        .having(vec![post::views::_avg::gt(20.0)])
+        .exec()
+        .await?;
+
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].published, Some(false));
+    assert_eq!(groups[0]._avg.views, Some(60.0));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn order_by_aggregate() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client).await?;
+
+    let groups = client
+        .post()
+        .group_by(vec![post::ScalarField::Published], vec![])
+        .select(vec![post::count_all()])
+        .order_by(post::views::_count::order(SortOrder::Desc))
+        .take(1)
+        .exec()
+        .await?;
+
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].published, Some(true));
+    assert_eq!(groups[0]._count._all, Some(2));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn order_by_nullable_field() -> TestResult {
+    let client = client().await;
+
+    client
+        .post()
+        .create_many(vec![
+            post::create_unchecked("Post 1".to_string(), true, vec![]),
+            post::create_unchecked(
+                "Post 2".to_string(),
+                true,
+                vec![post::desc::set(Some("a".to_string()))],
+            ),
+            post::create_unchecked(
+                "Post 3".to_string(),
+                false,
+                vec![post::desc::set(Some("b".to_string()))],
+            ),
+        ])
+        .exec()
+        .await?;
+
+    let posts = client
+        .post()
+        .find_many(vec![])
+        .order_by(post::desc::order(SortOrder::Desc))
+        .exec()
+        .await?;
+    assert_eq!(posts.len(), 3);
+
+    let groups = client
+        .post()
+        .group_by(vec![post::ScalarField::Desc], vec![])
+        .select(vec![post::count_all()])
+        .order_by(post::desc::order(SortOrder::Asc.nulls_first()))
+        .exec()
+        .await?;
+
+    assert_eq!(
+        groups.iter().map(|g| g.desc.clone()).collect::<Vec<_>>(),
+        [
+            Some(None),
+            Some(Some("a".to_string())),
+            Some(Some("b".to_string()))
+        ]
+    );
+
+    let groups = client
+        .post()
+        .group_by(vec![post::ScalarField::Desc], vec![])
+        .order_by(post::desc::order(SortOrder::Desc))
+        .exec()
+        .await?;
+    assert_eq!(groups.len(), 3);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn where_() -> TestResult {
+    let client = client().await;
+
+    create_posts(&client).await?;
+
+    let groups = client
+        .post()
+        .group_by(
+            vec![post::ScalarField::Published],
+            vec![post::views::gte(20)],
+        )
+        .select(vec![post::views::max()])
+        .order_by(post::published::order(SortOrder::Asc))
+        .exec()
+        .await?;
+
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0]._max.views, Some(60));
+    assert_eq!(groups[1]._max.views, Some(20));
+
+    cleanup(client).await
+}
@@ -142,3 +142,43 @@ async fn external_selection_args() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn relation_count() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    client
+        .post()
+        .create(
+            "post 1".to_string(),
+            true,
+            vec![post::author::connect(user::id::equals(user.id.clone()))],
+        )
+        .exec()
+        .await?;
+
+    let data = client
+        .user()
+        .find_unique(user::id::equals(user.id.clone()))
+        .include(user::include!({
+            posts: select {
+                id
+            }
+            _count(posts, favourite_posts)
+        }))
+        .exec()
+        .await?
+        .unwrap();
+    assert_eq!(data.id, user.id);
+    assert_eq!(data.posts.len(), 1);
+    assert_eq!(data._count.posts, 1);
+    assert_eq!(data._count.favourite_posts, 0);
+
+    cleanup(client).await
+}
@@ -0,0 +1,199 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Subscriber,
+};
+use tracing_subscriber::{layer::Context, prelude::*, Layer};
+
+use crate::{db::*, utils::*};
+
+type Fields = BTreeMap<String, String>;
+
+#[derive(Default)]
+struct FieldVisitor(Fields);
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{value:?}"));
+    }
+}
+
+/// Records the fields of spans and events, keeping spans once they're closed.
+#[derive(Default, Clone)]
+struct Recorder {
+    open: Arc<Mutex<HashMap<span::Id, (&'static str, Fields)>>>,
+    spans: Arc<Mutex<Vec<(&'static str, Fields)>>>,
+    events: Arc<Mutex<Vec<Fields>>>,
+}
+
+impl Recorder {
+    fn spans(&self, name: &str) -> Vec<Fields> {
+        self.spans
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(span_name, _)| *span_name == name)
+            .map(|(_, fields)| fields.clone())
+            .collect()
+    }
+
+    fn slow_queries(&self) -> Vec<Fields> {
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|fields| fields.get("message").map(String::as_str) == Some("slow query"))
+            .cloned()
+            .collect()
+    }
+}
+
+impl<S: Subscriber> Layer<S> for Recorder {
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, _: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        attrs.record(&mut visitor);
+
+        self.open
+            .lock()
+            .unwrap()
+            .insert(id.clone(), (attrs.metadata().name(), visitor.0));
+    }
+
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, _: Context<'_, S>) {
+        if let Some((_, fields)) = self.open.lock().unwrap().get_mut(id) {
+            let mut visitor = FieldVisitor::default();
+            values.record(&mut visitor);
+
+            fields.extend(visitor.0);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, _: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+
+        self.events.lock().unwrap().push(visitor.0);
+    }
+
+    fn on_close(&self, id: span::Id, _: Context<'_, S>) {
+        if let Some(span) = self.open.lock().unwrap().remove(&id) {
+            self.spans.lock().unwrap().push(span);
+        }
+    }
+}
+
+fn record() -> (Recorder, tracing::subscriber::DefaultGuard) {
+    let recorder = Recorder::default();
+
+    // tokio tests run on a single thread, so a thread local subscriber sees every query
+    let guard =
+        tracing::subscriber::set_default(tracing_subscriber::registry().with(recorder.clone()));
+
+    (recorder, guard)
+}
+
+#[tokio::test]
+async fn query_span_fields() -> TestResult {
+    let client = client().await;
+
+    let (recorder, _guard) = record();
+
+    client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+    client
+        .user()
+        .create("Oscar".to_string(), vec![])
+        .exec()
+        .await?;
+    client.user().find_many(vec![]).exec().await?;
+
+    let spans = recorder.spans("prisma_query");
+    assert_eq!(spans.len(), 3);
+
+    assert!(spans.iter().all(|span| span["model"] == "User"
+        && span.contains_key("duration_ms")
+        && !span.contains_key("tx_id")));
+
+    assert_eq!(spans[0]["action"], "createOne");
+    assert_eq!(spans[0]["rows"], "1");
+    assert_eq!(spans[2]["action"], "findMany");
+    assert_eq!(spans[2]["rows"], "2");
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn transaction_span_fields() -> TestResult {
+    let client = client().await;
+
+    let (recorder, _guard) = record();
+
+    client
+        ._transaction()
+        .run(|tx| async move { tx.user().create("Brendan".to_string(), vec![]).exec().await })
+        .await?;
+
+    let spans = recorder.spans("prisma_query");
+    assert_eq!(spans.len(), 1);
+    assert_eq!(spans[0]["model"], "User");
+    assert_eq!(spans[0]["action"], "createOne");
+    assert!(!spans[0]["tx_id"].is_empty());
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn slow_query_logged_above_threshold() -> TestResult {
+    let client = PrismaClient::_builder()
+        .slow_query_threshold(Duration::ZERO)
+        .build()
+        .await
+        .unwrap();
+
+    let (recorder, _guard) = record();
+
+    client
+        .user()
+        .find_many(vec![user::name::equals("Brendan".to_string())])
+        .exec()
+        .await?;
+
+    let slow_queries = recorder.slow_queries();
+    assert_eq!(slow_queries.len(), 1);
+    assert!(slow_queries[0]["arguments"].contains("Brendan"));
+    assert_eq!(slow_queries[0]["threshold_ms"], "0");
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn slow_query_not_logged_below_threshold() -> TestResult {
+    let client = PrismaClient::_builder()
+        .slow_query_threshold(Duration::from_secs(60 * 60))
+        .build()
+        .await
+        .unwrap();
+
+    let (recorder, _guard) = record();
+
+    client.user().find_many(vec![]).exec().await?;
+
+    assert_eq!(recorder.spans("prisma_query").len(), 1);
+    assert!(recorder.slow_queries().is_empty());
+
+    cleanup(client).await
+}
@@ -21,18 +21,27 @@ async fn aaaa_run_migrations() -> TestResult {
     cleanup(client).await
 }
 
+mod aggregate;
 mod batch;
-// mod callbacks;
+mod callbacks;
+mod client;
 mod count;
 mod create;
 mod create_many;
 mod delete;
 mod delete_many;
+mod errors;
+mod exists;
 mod find_first;
 mod find_many;
 mod find_unique;
+mod group_by;
 mod include;
+mod instrumentation;
+mod metrics;
+mod middleware;
 mod mock;
+mod nested_writes;
 mod partial;
 mod raw;
 mod select;
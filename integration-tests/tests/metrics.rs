@@ -0,0 +1,57 @@
+use crate::{db::*, utils::*};
+
+#[tokio::test]
+async fn queries() -> TestResult {
+    let client = client().await;
+
+    client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+    client.user().find_many(vec![]).exec().await?;
+    client.user().find_many(vec![]).exec().await?;
+
+    client
+        .user()
+        .update(user::id::equals("missing".to_string()), vec![])
+        .exec()
+        .await
+        .unwrap_err();
+
+    let metrics = client._metrics();
+
+    let find_many = &metrics.queries[&("User".to_string(), "findMany".to_string())];
+    assert_eq!(find_many.count, 2);
+    assert_eq!(find_many.errors, 0);
+    assert_eq!(find_many.duration.count, 2);
+
+    let update = &metrics.queries[&("User".to_string(), "updateOne".to_string())];
+    assert_eq!(update.errors, 1);
+    assert_eq!(metrics.errors["P2025"], 1);
+
+    let prometheus = metrics.to_prometheus();
+    assert!(
+        prometheus.contains("prisma_client_queries_total{model=\"User\",action=\"findMany\"} 2\n")
+    );
+    assert!(prometheus.contains("prisma_client_errors_total{code=\"P2025\"} 1\n"));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn transactions() -> TestResult {
+    let client = client().await;
+
+    let (tx, tx_client) = client._transaction().begin().await?;
+
+    assert_eq!(tx_client._metrics().transactions.in_flight, 1);
+
+    tx.commit(tx_client).await?;
+
+    let transactions = client._metrics().transactions;
+    assert_eq!(transactions.in_flight, 0);
+    assert_eq!(transactions.committed, 1);
+
+    cleanup(client).await
+}
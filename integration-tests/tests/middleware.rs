@@ -0,0 +1,149 @@
+use std::sync::{Arc, Mutex};
+
+use futures::future::BoxFuture;
+use prisma_client_rust::{
+    serde_value, Middleware, ModelOperation, ModelReadOperation, ModelWriteOperation, Next,
+    QueryContext,
+};
+
+use crate::{db::*, utils::*};
+
+#[derive(Default, Clone)]
+struct Recorder(Arc<Mutex<Vec<(Option<String>, Option<ModelOperation>)>>>);
+
+impl Middleware for Recorder {
+    fn handle<'a>(
+        &'a self,
+        ctx: QueryContext,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, prisma_client_rust::Result<serde_value::Value>> {
+        self.0.lock().unwrap().push((ctx.model.clone(), ctx.action));
+
+        next.run(ctx)
+    }
+}
+
+struct EmptyFindMany;
+
+impl Middleware for EmptyFindMany {
+    fn handle<'a>(
+        &'a self,
+        ctx: QueryContext,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, prisma_client_rust::Result<serde_value::Value>> {
+        match ctx.action {
+            Some(ModelOperation::Read(ModelReadOperation::FindMany)) => {
+                Box::pin(async { Ok(serde_value::Value::Seq(vec![])) })
+            }
+            _ => next.run(ctx),
+        }
+    }
+}
+
+#[tokio::test]
+async fn sees_model_and_action() -> TestResult {
+    cleanup(client().await).await?;
+
+    let recorder = Recorder::default();
+
+    let client = PrismaClient::_builder()
+        .with_middleware(recorder.clone())
+        .build()
+        .await
+        .unwrap();
+
+    client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+    client.user().find_many(vec![]).exec().await?;
+
+    assert_eq!(
+        *recorder.0.lock().unwrap(),
+        vec![
+            (
+                Some("User".to_string()),
+                Some(ModelOperation::Write(ModelWriteOperation::Create))
+            ),
+            (
+                Some("User".to_string()),
+                Some(ModelOperation::Read(ModelReadOperation::FindMany))
+            )
+        ]
+    );
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn sees_action_of_counts_and_selections() -> TestResult {
+    cleanup(client().await).await?;
+
+    let recorder = Recorder::default();
+
+    let client = PrismaClient::_builder()
+        .with_middleware(recorder.clone())
+        .build()
+        .await
+        .unwrap();
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+    recorder.0.lock().unwrap().clear();
+
+    client.user().count(vec![]).exec().await?;
+    client
+        .user()
+        .find_unique_or_throw(user::id::equals(user.id))
+        .select(user::select!({ name }))
+        .exec()
+        .await?;
+
+    assert_eq!(
+        *recorder.0.lock().unwrap(),
+        vec![
+            (
+                Some("User".to_string()),
+                Some(ModelOperation::Read(ModelReadOperation::Count))
+            ),
+            (
+                Some("User".to_string()),
+                Some(ModelOperation::Read(ModelReadOperation::FindUniqueOrThrow))
+            )
+        ]
+    );
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn short_circuit() -> TestResult {
+    cleanup(client().await).await?;
+
+    let recorder = Recorder::default();
+
+    let client = PrismaClient::_builder()
+        .with_middleware(EmptyFindMany)
+        .with_middleware(recorder.clone())
+        .build()
+        .await
+        .unwrap();
+
+    client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    let users = client.user().find_many(vec![]).exec().await?;
+    assert!(users.is_empty());
+
+    // the find_many never reached the second middleware
+    assert_eq!(recorder.0.lock().unwrap().len(), 1);
+
+    cleanup(client).await
+}
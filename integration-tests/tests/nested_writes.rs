@@ -0,0 +1,290 @@
+use crate::{db::*, utils::*};
+
+#[tokio::test]
+async fn create() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create(
+            "Brendan".to_string(),
+            vec![
+                user::profile::create(user::profile::Create::new(
+                    "My bio".to_string(),
+                    "Australia".to_string(),
+                    vec![],
+                )),
+                user::posts::create(vec![
+                    user::posts::Create::new("Post 1".to_string(), true, vec![]),
+                    user::posts::Create::new(
+                        "Post 2".to_string(),
+                        false,
+                        vec![post::views::set(5)],
+                    ),
+                ]),
+            ],
+        )
+        .with(user::profile::fetch())
+        .with(user::posts::fetch(vec![]))
+        .exec()
+        .await?;
+
+    let profile = user.profile.unwrap().unwrap();
+    assert_eq!(profile.bio, "My bio");
+    assert_eq!(profile.user_id, user.id);
+
+    let posts = user.posts.unwrap();
+    assert_eq!(posts.len(), 2);
+    assert!(posts
+        .iter()
+        .all(|post| post.author_id.as_ref() == Some(&user.id)));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn create_many() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create(
+            "Brendan".to_string(),
+            vec![user::posts::create_many(vec![
+                user::posts::CreateMany::new("Post 1".to_string(), true, vec![]),
+                user::posts::CreateMany::new("Post 2".to_string(), true, vec![]),
+            ])],
+        )
+        .exec()
+        .await?;
+
+    let count = client
+        .post()
+        .count(vec![post::author_id::equals(Some(user.id))])
+        .exec()
+        .await?;
+    assert_eq!(count, 2);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn create_many_with_other_required_relation() -> TestResult {
+    let client = client().await;
+
+    let author = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    let post = client
+        .post()
+        .create(
+            "Post".to_string(),
+            true,
+            vec![post::comments::create_many(vec![
+                post::comments::CreateMany::new("Comment 1".to_string(), author.id.clone(), vec![]),
+                post::comments::CreateMany::new("Comment 2".to_string(), author.id.clone(), vec![]),
+            ])],
+        )
+        .with(post::comments::fetch(vec![]))
+        .exec()
+        .await?;
+
+    let comments = post.comments.unwrap();
+    assert_eq!(comments.len(), 2);
+    assert!(comments
+        .iter()
+        .all(|comment| comment.author_id == author.id && comment.post_id == post.id));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn connect_or_create() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    let post = client
+        .post()
+        .create("Existing".to_string(), true, vec![])
+        .exec()
+        .await?;
+
+    client
+        .user()
+        .update(
+            user::id::equals(user.id.clone()),
+            vec![user::posts::connect_or_create(vec![
+                (
+                    post::id::equals(post.id.clone()),
+                    user::posts::Create::new("Existing".to_string(), true, vec![]),
+                ),
+                (
+                    post::id::equals("missing".to_string()),
+                    user::posts::Create::new("Created".to_string(), false, vec![]),
+                ),
+            ])],
+        )
+        .exec()
+        .await?;
+
+    let posts = client
+        .post()
+        .find_many(vec![post::author_id::equals(Some(user.id))])
+        .exec()
+        .await?;
+    assert_eq!(posts.len(), 2);
+    assert_eq!(client.post().count(vec![]).exec().await?, 2);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn update() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create(
+            "Brendan".to_string(),
+            vec![
+                user::profile::create(user::profile::Create::new(
+                    "My bio".to_string(),
+                    "Australia".to_string(),
+                    vec![],
+                )),
+                user::posts::create(vec![
+                    user::posts::Create::new("Post 1".to_string(), true, vec![]),
+                    user::posts::Create::new("Post 2".to_string(), true, vec![]),
+                ]),
+            ],
+        )
+        .with(user::posts::fetch(vec![]))
+        .exec()
+        .await?;
+
+    let first_post_id = user.posts.unwrap()[0].id.clone();
+
+    let user = client
+        .user()
+        .update(
+            user::id::equals(user.id),
+            vec![
+                user::profile::update(vec![profile::city::set(Some("Perth".to_string()))]),
+                user::posts::update(vec![(
+                    post::id::equals(first_post_id.clone()),
+                    vec![post::views::increment(10)],
+                )]),
+                user::posts::update_many(vec![], vec![post::published::set(false)]),
+            ],
+        )
+        .with(user::profile::fetch())
+        .with(user::posts::fetch(vec![]))
+        .exec()
+        .await?;
+
+    let profile = user.profile.unwrap().unwrap();
+    assert_eq!(profile.city.as_deref(), Some("Perth"));
+
+    let posts = user.posts.unwrap();
+    assert!(posts.iter().all(|post| !post.published));
+    assert_eq!(
+        posts
+            .iter()
+            .find(|post| post.id == first_post_id)
+            .unwrap()
+            .views,
+        10
+    );
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn upsert() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    let upsert = || {
+        user::profile::upsert(
+            user::profile::Create::new("Created".to_string(), "Australia".to_string(), vec![]),
+            vec![profile::bio::set("Updated".to_string())],
+        )
+    };
+
+    let user = client
+        .user()
+        .update(user::id::equals(user.id), vec![upsert()])
+        .with(user::profile::fetch())
+        .exec()
+        .await?;
+    assert_eq!(user.profile.unwrap().unwrap().bio, "Created");
+
+    let user = client
+        .user()
+        .update(user::id::equals(user.id), vec![upsert()])
+        .with(user::profile::fetch())
+        .exec()
+        .await?;
+    assert_eq!(user.profile.unwrap().unwrap().bio, "Updated");
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn delete() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create(
+            "Brendan".to_string(),
+            vec![
+                user::profile::create(user::profile::Create::new(
+                    "My bio".to_string(),
+                    "Australia".to_string(),
+                    vec![],
+                )),
+                user::posts::create(vec![
+                    user::posts::Create::new("Post 1".to_string(), true, vec![]),
+                    user::posts::Create::new("Post 2".to_string(), false, vec![]),
+                    user::posts::Create::new("Post 3".to_string(), false, vec![]),
+                ]),
+            ],
+        )
+        .with(user::posts::fetch(vec![]))
+        .exec()
+        .await?;
+
+    let first_post_id = user.posts.unwrap()[0].id.clone();
+
+    client
+        .user()
+        .update(
+            user::id::equals(user.id),
+            vec![
+                user::profile::delete(),
+                user::posts::delete(vec![post::id::equals(first_post_id)]),
+                user::posts::delete_many(vec![post::published::equals(false)]),
+            ],
+        )
+        .exec()
+        .await?;
+
+    assert_eq!(client.profile().count(vec![]).exec().await?, 0);
+    assert_eq!(client.post().count(vec![]).exec().await?, 0);
+
+    cleanup(client).await
+}
@@ -93,3 +93,56 @@ async fn relation() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn nulls() -> TestResult {
+    let client = client().await;
+
+    client
+        .post()
+        .create_many(vec![
+            post::create_unchecked(
+                "Post 1".to_string(),
+                false,
+                vec![post::desc::set(Some("a".to_string()))],
+            ),
+            post::create_unchecked("Post 2".to_string(), false, vec![]),
+            post::create_unchecked(
+                "Post 3".to_string(),
+                false,
+                vec![post::desc::set(Some("b".to_string()))],
+            ),
+        ])
+        .exec()
+        .await?;
+
+    let nulls_first = client
+        .post()
+        .find_many(vec![])
+        .order_by(post::desc::order(SortOrder::Asc.nulls_first()))
+        .exec()
+        .await?;
+    assert_eq!(
+        nulls_first
+            .iter()
+            .map(|post| post.title.as_str())
+            .collect::<Vec<_>>(),
+        vec!["Post 2", "Post 1", "Post 3"]
+    );
+
+    let nulls_last = client
+        .post()
+        .find_many(vec![])
+        .order_by(post::desc::order(SortOrder::Asc.nulls_last()))
+        .exec()
+        .await?;
+    assert_eq!(
+        nulls_last
+            .iter()
+            .map(|post| post.title.as_str())
+            .collect::<Vec<_>>(),
+        vec!["Post 1", "Post 3", "Post 2"]
+    );
+
+    cleanup(client).await
+}
@@ -167,3 +167,56 @@ async fn external_selection_arguments() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn relation_count() -> TestResult {
+    let client = client().await;
+
+    let user = client
+        .user()
+        .create("Brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    for (title, published) in [("post 1", true), ("post 2", false), ("post 3", true)] {
+        client
+            .post()
+            .create(
+                title.to_string(),
+                published,
+                vec![post::author::connect(user::id::equals(user.id.clone()))],
+            )
+            .exec()
+            .await?;
+    }
+
+    let data = client
+        .user()
+        .find_unique(user::id::equals(user.id.clone()))
+        .select(user::select!({
+            name
+            _count(
+                posts,
+                favourite_posts: vec![post::published::equals(true)]
+            )
+        }))
+        .exec()
+        .await?
+        .unwrap();
+    assert_eq!(data.name, "Brendan");
+    assert_eq!(data._count.posts, 3);
+    assert_eq!(data._count.favourite_posts, 0);
+
+    let data = client
+        .user()
+        .find_unique(user::id::equals(user.id))
+        .select(user::select!({
+            _count(posts: vec![post::published::equals(true)])
+        }))
+        .exec()
+        .await?
+        .unwrap();
+    assert_eq!(data._count.posts, 2);
+
+    cleanup(client).await
+}
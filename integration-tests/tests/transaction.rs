@@ -1,6 +1,12 @@
-use std::time::Duration;
+use std::{
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
 
-use prisma_client_rust::QueryError;
+use prisma_client_rust::{raw, Backoff, QueryError};
 
 use crate::db::*;
 use crate::utils::*;
@@ -166,6 +172,235 @@ async fn batch() -> TestResult {
     cleanup(client).await
 }
 
+#[tokio::test]
+async fn batch_rollback() -> TestResult {
+    let client = client().await;
+
+    let result = client
+        ._transaction()
+        .run(|client| async move {
+            client
+                ._batch(vec![
+                    client.user().create("brendan".to_string(), vec![]),
+                    client.user().create("oscar".to_string(), vec![]),
+                ])
+                .await?;
+
+            client
+                .post()
+                .create(
+                    "test".to_string(),
+                    true,
+                    vec![post::author::connect(user::id::equals("".to_string()))],
+                )
+                .exec()
+                .await
+        })
+        .await;
+
+    assert!(result.is_err());
+    // the batch ran in the transaction, so its users were rolled back too
+    assert!(client.user().find_many(vec![]).exec().await?.is_empty());
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn retries() -> TestResult {
+    let client = client().await;
+
+    let attempts = AtomicU32::new(0);
+
+    let user = client
+        ._transaction()
+        .with_retries(3, Backoff::Fixed(Duration::from_millis(10)))
+        .retry_if(|_: &QueryError| true)
+        .run(|client| {
+            let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+
+            async move {
+                let user = client
+                    .user()
+                    .create("brendan".to_string(), vec![])
+                    .exec()
+                    .await?;
+
+                // fail the first two attempts by connecting a post to a missing user
+                let author = match attempt {
+                    0 | 1 => "".to_string(),
+                    _ => user.id.clone(),
+                };
+
+                client
+                    .post()
+                    .create(
+                        "test".to_string(),
+                        true,
+                        vec![post::author::connect(user::id::equals(author))],
+                    )
+                    .exec()
+                    .await
+                    .map(|_| user)
+            }
+        })
+        .await?;
+
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    assert_eq!(client.user().count(vec![]).exec().await?, 1);
+    assert_eq!(&user.name, "brendan");
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn retries_only_retryable() -> TestResult {
+    let client = client().await;
+
+    let attempts = AtomicU32::new(0);
+
+    let result = client
+        ._transaction()
+        .with_retries(3, Backoff::None)
+        .run(|client| {
+            attempts.fetch_add(1, Ordering::SeqCst);
+
+            async move {
+                client
+                    .post()
+                    .create(
+                        "test".to_string(),
+                        true,
+                        vec![post::author::connect(user::id::equals("".to_string()))],
+                    )
+                    .exec()
+                    .await
+            }
+        })
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn nested() -> TestResult {
+    let client = client().await;
+
+    client
+        ._transaction()
+        .run(|client| async move {
+            let user = client
+                .user()
+                .create("brendan".to_string(), vec![])
+                .exec()
+                .await?;
+            let user_id = &user.id;
+
+            let nested = client
+                ._transaction()
+                .run(|client| async move {
+                    client
+                        .post()
+                        .create(
+                            "first".to_string(),
+                            true,
+                            vec![post::author::connect(user::id::equals(user_id.clone()))],
+                        )
+                        .exec()
+                        .await?;
+
+                    client
+                        .post()
+                        .create(
+                            "second".to_string(),
+                            true,
+                            vec![post::author::connect(user::id::equals("".to_string()))],
+                        )
+                        .exec()
+                        .await
+                })
+                .await;
+
+            // only the nested transaction is rolled back
+            assert!(nested.is_err());
+
+            client
+                ._transaction()
+                .run(|client| async move {
+                    client
+                        .post()
+                        .create(
+                            "third".to_string(),
+                            true,
+                            vec![post::author::connect(user::id::equals(user_id.clone()))],
+                        )
+                        .exec()
+                        .await
+                })
+                .await
+        })
+        .await?;
+
+    assert_eq!(client.user().count(vec![]).exec().await?, 1);
+
+    let posts = client.post().find_many(vec![]).exec().await?;
+    assert_eq!(posts.len(), 1);
+    assert_eq!(&posts[0].title, "third");
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn nested_options() -> TestResult {
+    let client = client().await;
+
+    let result = client
+        ._transaction()
+        .run(|client| async move {
+            client
+                ._transaction()
+                .with_timeout(10000)
+                .run(|client| async move { client.user().count(vec![]).exec().await })
+                .await
+        })
+        .await;
+
+    assert!(matches!(result, Err(QueryError::InvalidArgument(_))));
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn nested_rollback_error() -> TestResult {
+    let client = client().await;
+
+    let result = client
+        ._transaction()
+        .run(|client| async move {
+            client
+                ._transaction()
+                .run(|client| async move {
+                    // removes the savepoint so that rolling back to it fails
+                    client
+                        ._execute_raw(raw!("RELEASE SAVEPOINT prisma_savepoint_1"))
+                        .exec()
+                        .await?;
+
+                    Err::<(), _>(QueryError::Middleware("rejected".to_string()))
+                })
+                .await
+        })
+        .await;
+
+    // the rollback's error is returned rather than the closure's
+    assert!(matches!(result, Err(QueryError::Execute(_))));
+    assert_eq!(client.user().count(vec![]).exec().await?, 0);
+
+    cleanup(client).await
+}
+
 // Imperative
 
 #[tokio::test]
@@ -256,3 +491,80 @@ async fn rollback() -> TestResult {
 
     cleanup(client).await
 }
+
+#[tokio::test]
+async fn drop_rollback() -> TestResult {
+    let client = client().await;
+
+    {
+        let (_tx, client) = client._transaction().begin().await?;
+
+        client
+            .user()
+            .create("brendan".to_string(), vec![])
+            .exec()
+            .await?;
+    }
+
+    // the rollback is spawned when the controller is dropped
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    assert!(client.user().find_many(vec![]).exec().await?.is_empty());
+    assert_eq!(client._metrics().transactions.in_flight, 0);
+
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn hooks() -> TestResult {
+    let client = client().await;
+
+    let events = Arc::new(Mutex::new(vec![]));
+
+    let hook_events = events.clone();
+    let result = client
+        ._transaction()
+        .run(|client| async move {
+            let events = hook_events.clone();
+            client._on_commit(move || events.lock().unwrap().push("first commit"));
+            let events = hook_events.clone();
+            client._on_rollback(move || events.lock().unwrap().push("first rollback"));
+
+            client
+                .post()
+                .create(
+                    "test".to_string(),
+                    true,
+                    vec![post::author::connect(user::id::equals("".to_string()))],
+                )
+                .exec()
+                .await
+        })
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(*events.lock().unwrap(), vec!["first rollback"]);
+
+    let (tx, tx_client) = client._transaction().begin().await?;
+
+    let hook_events = events.clone();
+    tx_client._on_commit(move || hook_events.lock().unwrap().push("second commit"));
+
+    tx_client
+        .user()
+        .create("brendan".to_string(), vec![])
+        .exec()
+        .await?;
+
+    // hooks don't run until the transaction commits
+    assert_eq!(events.lock().unwrap().len(), 1);
+
+    tx.commit(tx_client).await?;
+
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec!["first rollback", "second commit"]
+    );
+
+    cleanup(client).await
+}
@@ -8,6 +8,7 @@ pub async fn client() -> PrismaClient {
 
     client
         ._batch((
+            client.comment().delete_many(vec![]),
             client.file_path().delete_many(vec![]),
             client.category().delete_many(vec![]),
             client.post().delete_many(vec![]),
@@ -24,6 +25,7 @@ pub async fn client() -> PrismaClient {
 pub async fn cleanup(client: PrismaClient) -> TestResult {
     client
         ._batch((
+            client.comment().delete_many(vec![]),
             client.file_path().delete_many(vec![]),
             client.category().delete_many(vec![]),
             client.post().delete_many(vec![]),
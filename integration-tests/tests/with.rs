@@ -153,6 +153,24 @@ async fn take() -> TestResult {
     cleanup(client).await
 }
 
+#[tokio::test]
+async fn distinct() -> TestResult {
+    let client = client().await;
+
+    let user_id = setup(&client).await?;
+
+    let user = client
+        .user()
+        .find_unique(user::id::equals(user_id.clone()))
+        .with(user::posts::fetch(vec![]).distinct(vec![post::ScalarField::Published]))
+        .exec()
+        .await?
+        .unwrap();
+    assert_eq!(user.posts.unwrap().len(), 2);
+
+    cleanup(client).await
+}
+
 #[tokio::test]
 async fn where_() -> TestResult {
     let client = client().await;
@@ -280,6 +298,51 @@ async fn nested_with() -> TestResult {
     cleanup(client).await
 }
 
+#[tokio::test]
+async fn relation_counts() -> TestResult {
+    let client = client().await;
+
+    let user_id = setup(&client).await?;
+
+    let user = client
+        .user()
+        .find_unique(user::id::equals(user_id.clone()))
+        .with(user::relation_counts(vec![
+            user::posts::count(vec![post::published::equals(true)]),
+            user::favourite_posts::count(vec![]),
+        ]))
+        .with(
+            user::posts::fetch(vec![])
+                .with(post::relation_counts(vec![post::categories::count(vec![])])),
+        )
+        .exec()
+        .await?
+        .unwrap();
+
+    let count = user._count.unwrap();
+    assert_eq!(count.posts, Some(2));
+    assert_eq!(count.favourite_posts, Some(0));
+
+    let categories = user
+        .posts
+        .unwrap()
+        .into_iter()
+        .map(|post| post._count.unwrap().categories.unwrap())
+        .sum::<i64>();
+    assert_eq!(categories, 2);
+
+    // counts aren't fetched unless requested
+    let user = client
+        .user()
+        .find_unique(user::id::equals(user_id))
+        .exec()
+        .await?
+        .unwrap();
+    assert!(user._count.is_none());
+
+    cleanup(client).await
+}
+
 // TODO: Nested create
 
 // #[tokio::test]
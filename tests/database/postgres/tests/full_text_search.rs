@@ -0,0 +1,87 @@
+use crate::{db::*, utils::*};
+
+async fn create_articles(client: &PrismaClient) -> TestResult {
+    client
+        .article()
+        .create_many(vec![
+            article::create_unchecked(
+                "Rust".to_string(),
+                "Rust is fast. Rust is safe. Rust is fun.".to_string(),
+                vec![],
+            ),
+            article::create_unchecked(
+                "Languages".to_string(),
+                "Rust and Go are both compiled".to_string(),
+                vec![],
+            ),
+            article::create_unchecked("Go".to_string(), "Go has goroutines".to_string(), vec![]),
+        ])
+        .exec()
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn search() -> TestResult {
+    let client = client().await;
+
+    client.article().delete_many(vec![]).exec().await?;
+    create_articles(&client).await?;
+
+    let mut titles = client
+        .article()
+        .find_many(vec![article::body::search("rust".to_string())])
+        .exec()
+        .await?
+        .into_iter()
+        .map(|article| article.title)
+        .collect::<Vec<_>>();
+    titles.sort();
+    assert_eq!(titles, ["Languages", "Rust"]);
+
+    client.article().delete_many(vec![]).exec().await?;
+    cleanup(client).await
+}
+
+#[tokio::test]
+async fn order_by_relevance() -> TestResult {
+    let client = client().await;
+
+    client.article().delete_many(vec![]).exec().await?;
+    create_articles(&client).await?;
+
+    let titles = |articles: Vec<article::Data>| {
+        articles
+            .into_iter()
+            .map(|article| article.title)
+            .collect::<Vec<_>>()
+    };
+
+    let most_relevant = client
+        .article()
+        .find_many(vec![article::body::search("rust".to_string())])
+        .order_by(article::relevance(
+            vec![article::RelevanceField::Body],
+            "rust",
+            SortOrder::Desc,
+        ))
+        .exec()
+        .await?;
+    assert_eq!(titles(most_relevant), ["Rust", "Languages"]);
+
+    let least_relevant = client
+        .article()
+        .find_many(vec![article::body::search("rust".to_string())])
+        .order_by(article::relevance(
+            vec![article::RelevanceField::Body],
+            "rust",
+            SortOrder::Asc,
+        ))
+        .exec()
+        .await?;
+    assert_eq!(titles(least_relevant), ["Languages", "Rust"]);
+
+    client.article().delete_many(vec![]).exec().await?;
+    cleanup(client).await
+}
@@ -1,5 +1,7 @@
 #[allow(warnings, unused)]
 mod db;
+mod full_text_search;
+mod timeout;
 mod utils;
 
 use db::*;
@@ -0,0 +1,40 @@
+use std::time::{Duration, Instant};
+
+use prisma_client_rust::{raw, serde_json, QueryError};
+
+use crate::{db::*, utils::*};
+
+#[tokio::test]
+async fn timeout_cancels_statement() -> TestResult {
+    // a single connection, so the next query can only run once it's been released
+    let client = PrismaClient::_builder()
+        .connection_limit(1)
+        .build()
+        .await
+        .unwrap();
+
+    let start = Instant::now();
+
+    let error = client
+        ._query_raw::<serde_json::Value>(raw!("SELECT pg_sleep(10)"))
+        .timeout(Duration::from_millis(200))
+        .exec()
+        .await
+        .unwrap_err();
+    assert!(matches!(error, QueryError::Timeout(_)));
+
+    // the connection is usable long before the statement would have finished
+    let sleeping: Vec<serde_json::Value> = client
+        ._query_raw(raw!(
+            "SELECT pid FROM pg_stat_activity \
+            WHERE state = 'active' AND query LIKE 'SELECT pg_sleep%'"
+        ))
+        .exec()
+        .await?;
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    // and the database stopped executing it
+    assert!(sleeping.is_empty());
+
+    cleanup(client).await
+}